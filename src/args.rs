@@ -1,6 +1,6 @@
 use std::{env, fs, path::PathBuf};
 
-use clap::Parser;
+use clap::{Parser, Subcommand};
 use serde::{Deserialize, Serialize};
 
 use crate::config::Config;
@@ -9,7 +9,8 @@ use crate::config::Config;
 #[clap(
     author = "Krypton (https://krypton.ninja)",
     about,
-    arg_required_else_help(true)
+    arg_required_else_help(true),
+    subcommand_negates_reqs(true)
 )]
 pub struct Args {
     /// Domain to scan for
@@ -57,6 +58,18 @@ pub struct Args {
     /// Whether to print some verbose data
     #[arg(short = 'v', long, default_value_t = false)]
     pub verbose: bool,
+
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Subcommand)]
+pub enum Command {
+    /// Validate a domain takeover fingerprints file
+    ValidateFingerprints {
+        /// The path to the TOML or JSON fingerprints file
+        path: String,
+    },
 }
 
 impl Args {
@@ -1,19 +1,77 @@
-use std::{env, fs, path::PathBuf};
+use std::{collections::HashMap, env, fs, path::PathBuf};
 
 use serde::{Deserialize, Serialize};
 
 use crate::{logger, modules::dork};
 
-const DEFAULT_CONFIG: &str = r#"[domain_takeover]
+const DEFAULT_CONFIG: &str = r#"[cloud_detect]
+enabled = false
+
+[dns_axfr]
+enabled = false
+
+[dns_bruteforce]
+enabled = false
+
+[dns_permutations]
+enabled = false
+
+[dns_records]
+enabled = false
+
+[dns_reverse]
+enabled = false
+
+[domain_takeover]
+enabled = false
+
+[domain_whois]
 enabled = false
 
 [dork]
 enabled = false
 
+[email_security]
+enabled = false
+
+[http_bruteforce]
+enabled = false
+
+[http_crawler]
+enabled = false
+
+[http_files]
+enabled = false
+
+[http_headers]
+enabled = false
+
+[http_javascript]
+enabled = false
+
+[http_probe]
+enabled = false
+
+[http_robots]
+enabled = false
+
 [passive_dns]
 enabled = false
+
+[port_scan]
+enabled = false
+
+[service_detection]
+enabled = false
+
+[tls_inspect]
+enabled = false
+
+[tls_scan]
+enabled = false
 "#;
 
+#[allow(clippy::collapsible_if)]
 pub fn create_file_if_not_existing() {
     let home_dir = env::var("HOME")
         .or_else(|_| env::var("USERPROFILE"))
@@ -40,15 +98,121 @@ pub fn create_file_if_not_existing() {
 /// The config.toml file structure
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Config {
+    pub cloud_detect: Option<CloudDetectConfig>,
+    pub dns_axfr: Option<DNSAxfrConfig>,
+    pub dns_bruteforce: Option<DNSBruteforceConfig>,
+    pub dns_permutations: Option<DNSPermutationsConfig>,
+    pub dns_records: Option<DNSRecordsConfig>,
+    pub dns_reverse: Option<DNSReverseConfig>,
     pub domain_takeover: Option<DomainTakeoverConfig>,
+    pub domain_whois: Option<DomainWhoisConfig>,
     pub dork: Option<DorkConfig>,
+    pub email_security: Option<EmailSecurityConfig>,
+    pub geoip: Option<GeoIpConfig>,
+    pub http_bruteforce: Option<HttpBruteforceConfig>,
+    pub http_crawler: Option<HttpCrawlerConfig>,
+    pub http_files: Option<HttpFilesConfig>,
+    pub http_headers: Option<HttpHeadersConfig>,
+    pub http_javascript: Option<HttpJavascriptConfig>,
+    pub http_probe: Option<HttpProbeConfig>,
+    pub http_robots: Option<HttpRobotsConfig>,
     pub passive_dns: Option<PassiveDNSConfig>,
+    pub port_scan: Option<PortScanConfig>,
+    pub resolver: Option<ResolverConfig>,
+    pub service_detection: Option<ServiceDetectionConfig>,
+    pub tls_inspect: Option<TlsInspectConfig>,
+    pub tls_scan: Option<TlsScanConfig>,
+    pub wildcard: Option<WildcardConfig>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CloudDetectConfig {
+    /// Whether the module is enabled
+    pub enabled: bool,
+    /// The paths to the IP range files published by the providers, keyed by the name of the provider
+    pub ranges: Option<HashMap<String, String>>,
+    /// The path to a JSON file with the CDN and WAF signatures, instead of the embedded ones
+    pub signatures: Option<String>,
+    /// The timeout of each request, in seconds
+    pub timeout: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DNSAxfrConfig {
+    /// Whether the module is enabled
+    pub enabled: bool,
+    /// The port on which the nameservers are asked for a zone transfer
+    pub port: Option<u16>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DNSBruteforceConfig {
+    /// Whether the module is enabled
+    pub enabled: bool,
+    /// The path to the wordlist to use, instead of the one given with `--wordlist`
+    pub wordlist: Option<String>,
+    /// How many levels below the target domain discovered subdomains will be brute-forced as well
+    pub recursion_depth: Option<usize>,
+    /// The amount of resolutions performed at the same time
+    pub concurrency: Option<usize>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DNSPermutationsConfig {
+    /// Whether the module is enabled
+    pub enabled: bool,
+    /// The path to a wordlist of words to insert and swap, instead of the built-in one
+    pub wordlist: Option<String>,
+    /// The maximum amount of permutations generated for each discovered domain
+    pub max_candidates: Option<usize>,
+    /// The amount of resolutions performed at the same time
+    pub concurrency: Option<usize>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DNSRecordsConfig {
+    /// Whether the module is enabled
+    pub enabled: bool,
+    /// The service names probed for SRV records, e.g. `_sip._tcp`
+    pub srv: Option<Vec<String>>,
+    /// The amount of SRV names queried at the same time
+    pub concurrency: Option<usize>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DNSReverseConfig {
+    /// Whether the module is enabled
+    pub enabled: bool,
+    /// Whether to look up the neighbors of the addresses whose network belongs to one of the organizations
+    pub sweep: Option<bool>,
+    /// The prefix length of the networks swept around an address, between 24 and 32
+    pub prefix: Option<u8>,
+    /// The organizations owning the networks to sweep, matched against the autonomous system of the addresses
+    pub organizations: Option<Vec<String>>,
+    /// The amount of PTR lookups performed at the same time
+    pub concurrency: Option<usize>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct DomainTakeoverConfig {
     /// Whether the module is enabled
     pub enabled: bool,
+    /// The path to a TOML or JSON fingerprints file, instead of the built-in one
+    pub fingerprints: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DomainWhoisConfig {
+    /// Whether the module is enabled
+    pub enabled: bool,
+    /// The base URL of the RDAP server queried for every domain, instead of the one of its TLD
+    pub rdap_server: Option<String>,
+    /// The WHOIS server queried when RDAP fails, as `host` or `host:port`, instead of the one of its TLD
+    pub whois_server: Option<String>,
+    /// The amount of days before the expiry of a domain from which it's flagged as expiring
+    pub expiry_window: Option<i64>,
+    /// The timeout of each query, in milliseconds
+    pub timeout: Option<u64>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -59,6 +223,114 @@ pub struct DorkConfig {
     pub search_engine: Option<dork::SearchEngine>,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct EmailSecurityConfig {
+    /// Whether the module is enabled
+    pub enabled: bool,
+    /// The DKIM selectors to probe, e.g. `google` or `selector1`
+    pub dkim_selectors: Option<Vec<String>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct GeoIpConfig {
+    /// The path to a GeoLite2-City or compatible MMDB file
+    pub city: Option<String>,
+    /// The path to a GeoLite2-ASN or compatible MMDB file
+    pub asn: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct HttpBruteforceConfig {
+    /// Whether the module is enabled
+    pub enabled: bool,
+    /// The path to the wordlist to use, instead of the one given with `--wordlist`
+    pub wordlist: Option<String>,
+    /// The extensions appended to every entry of the wordlist, e.g. `php` or `bak`
+    pub extensions: Option<Vec<String>>,
+    /// The status codes of the responses considered as found
+    pub status_codes: Option<Vec<u16>>,
+    /// The timeout of each request, in seconds
+    pub timeout: Option<u64>,
+    /// The amount of paths requested at the same time on a web server
+    pub concurrency: Option<usize>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct HttpCrawlerConfig {
+    /// Whether the module is enabled
+    pub enabled: bool,
+    /// The maximum amount of links followed from the first page of a web server
+    pub depth: Option<usize>,
+    /// The maximum amount of pages requested on a web server
+    pub max_pages: Option<usize>,
+    /// The maximum amount of requests sent to a host per second
+    pub rate: Option<u32>,
+    /// The timeout of each request, in seconds
+    pub timeout: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct HttpFilesConfig {
+    /// Whether the module is enabled
+    pub enabled: bool,
+    /// The path to a TOML or JSON file with more paths to check, in addition to the built-in ones
+    pub paths: Option<String>,
+    /// The timeout of each request, in seconds
+    pub timeout: Option<u64>,
+    /// The amount of paths checked at the same time on a web server
+    pub concurrency: Option<usize>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct HttpHeadersConfig {
+    /// Whether the module is enabled
+    pub enabled: bool,
+    /// The timeout of the request, in seconds
+    pub timeout: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct HttpJavascriptConfig {
+    /// Whether the module is enabled
+    pub enabled: bool,
+    /// Whether the scripts hosted outside of the target domain are analyzed as well
+    pub third_party: Option<bool>,
+    /// The maximum amount of scripts analyzed on a web server
+    pub max_scripts: Option<usize>,
+    /// The maximum size of a script or source map, in kilobytes
+    pub max_size: Option<u64>,
+    /// The path to a TOML or JSON file with more secret patterns, in addition to the built-in ones
+    pub secrets: Option<String>,
+    /// The timeout of each request, in seconds
+    pub timeout: Option<u64>,
+    /// The amount of scripts downloaded at the same time on a web server
+    pub concurrency: Option<usize>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct HttpProbeConfig {
+    /// Whether the module is enabled
+    pub enabled: bool,
+    /// The timeout of each request, in seconds
+    pub timeout: Option<u64>,
+    /// The maximum amount of redirects to follow
+    pub max_redirects: Option<usize>,
+    /// The path to a JSON file with the technology signatures, instead of the embedded ones
+    pub signatures: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct HttpRobotsConfig {
+    /// Whether the module is enabled
+    pub enabled: bool,
+    /// The maximum amount of sitemaps fetched on a web server, nested ones included
+    pub max_sitemaps: Option<usize>,
+    /// The maximum amount of URLs saved for a web server
+    pub max_urls: Option<usize>,
+    /// The timeout of each request, in seconds
+    pub timeout: Option<u64>,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct PassiveDNSConfig {
     /// Whether the module is enabled
@@ -68,3 +340,57 @@ pub struct PassiveDNSConfig {
     /// Only care about the recently (24 hours) created certificates
     pub recent_only: Option<bool>,
 }
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PortScanConfig {
+    /// Whether the module is enabled
+    pub enabled: bool,
+    /// The ports to scan, e.g. `top-100`, `1-1024` or `22,80,443,8000-8100`
+    pub ports: Option<String>,
+    /// The connection timeout for each port, in milliseconds
+    pub timeout: Option<u64>,
+    /// The amount of ports scanned at the same time on a host
+    pub concurrency: Option<usize>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ResolverConfig {
+    /// The nameservers to query, with an optional port, instead of the ones from `/etc/resolv.conf`
+    pub nameservers: Option<Vec<String>>,
+    /// The timeout of each query, in milliseconds
+    pub timeout: Option<u64>,
+    /// How many times the nameservers are queried before giving up
+    pub attempts: Option<usize>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ServiceDetectionConfig {
+    /// Whether the module is enabled
+    pub enabled: bool,
+    /// The connection and read timeout for each probe, in milliseconds
+    pub timeout: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TlsInspectConfig {
+    /// Whether the module is enabled
+    pub enabled: bool,
+    /// The connection and handshake timeout, in milliseconds
+    pub timeout: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TlsScanConfig {
+    /// Whether the module is enabled
+    pub enabled: bool,
+    /// The connection and read timeout for each handshake, in milliseconds
+    pub timeout: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct WildcardConfig {
+    /// Drop the discovered domains matching a wildcard record instead of flagging them
+    pub drop: Option<bool>,
+    /// The amount of random labels to resolve to detect a wildcard record
+    pub probes: Option<usize>,
+}
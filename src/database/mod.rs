@@ -1,4 +1,6 @@
-use node::{Node, Type};
+use std::collections::BTreeMap;
+
+use node::{HeadersSummary, Host, Node, Type};
 
 pub mod node;
 #[cfg(test)]
@@ -30,4 +32,94 @@ impl Database {
     pub fn search(&mut self, r#type: Type, value: String) -> Option<&mut Node> {
         self.root.find(&Node::new(r#type, value))
     }
+
+    /// Returns the addresses the discovered domains resolve to, sorted by address
+    pub fn get_hosts(&self) -> Vec<Host> {
+        let mut hosts = BTreeMap::new();
+        self.root.collect_hosts(&mut hosts);
+        hosts.into_values().collect()
+    }
+
+    /// Returns the grades of the security headers of the web servers, sorted by URL
+    pub fn get_headers_summaries(&self) -> Vec<HeadersSummary> {
+        self.root
+            .get_data("security_headers")
+            .and_then(|summaries| serde_json::from_value(summaries.clone()).ok())
+            .unwrap_or_default()
+    }
+
+    /// Saves the grade of the security headers of a web server on the root node, so that it is part of the JSON result
+    pub fn add_headers_summary(&mut self, summary: HeadersSummary) {
+        let mut summaries = self.get_headers_summaries();
+        summaries.retain(|existing| existing.url != summary.url);
+        summaries.push(summary);
+        summaries.sort_by(|a, b| a.url.cmp(&b.url));
+        if let Ok(summaries) = serde_json::to_value(summaries) {
+            self.root
+                .add_data(String::from("security_headers"), summaries);
+        }
+    }
+}
+
+/// Renders the hosts grouped by address and by provider
+pub fn hosts_to_markdown(hosts: &[Host]) -> String {
+    let mut result = String::from(
+        "### Hosts\n\n| Address | Netblock | Provider | Domains |\n| --- | --- | --- | --- |",
+    );
+    let mut providers: BTreeMap<String, (Vec<&str>, Vec<&str>)> = BTreeMap::new();
+    for host in hosts {
+        let provider = match (&host.asn, &host.organization) {
+            (Some(asn), Some(organization)) => format!("{} {}", asn, organization),
+            (Some(asn), None) => asn.clone(),
+            _ => String::from("Unknown"),
+        };
+        result += format!(
+            "\n| `{}` | {} | {} | {} |",
+            host.ip,
+            host.netblock
+                .as_ref()
+                .map(|netblock| format!("`{}`", netblock))
+                .unwrap_or_default(),
+            provider,
+            host.domains
+                .iter()
+                .map(|domain| format!("`{}`", domain))
+                .collect::<Vec<String>>()
+                .join(", ")
+        )
+        .as_str();
+        let (addresses, domains) = providers.entry(provider).or_default();
+        addresses.push(&host.ip);
+        for domain in &host.domains {
+            if !domains.contains(&domain.as_str()) {
+                domains.push(domain);
+            }
+        }
+    }
+
+    result += "\n\n### Providers\n\n| Provider | Addresses | Domains |\n| --- | --- | --- |";
+    for (provider, (addresses, domains)) in providers {
+        result += format!(
+            "\n| {} | {} | {} |",
+            provider,
+            addresses.len(),
+            domains.len()
+        )
+        .as_str();
+    }
+    result
+}
+
+/// Renders the grades of the security headers of the web servers
+pub fn headers_summaries_to_markdown(summaries: &[HeadersSummary]) -> String {
+    let mut result =
+        String::from("| URL | Domain | Grade | Score | Issues |\n| --- | --- | --- | --- | --- |");
+    for summary in summaries {
+        result += format!(
+            "\n| {} | `{}` | {} | {} | {} |",
+            summary.url, summary.domain, summary.grade, summary.score, summary.issues
+        )
+        .as_str();
+    }
+    result
 }
@@ -1,14 +1,22 @@
-use std::{collections::HashMap, fmt};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::net::IpAddr;
 
-use serde::{Serialize, Serializer};
+use serde::{Deserialize, Serialize, Serializer};
 use serde_json::Value;
 
+use crate::findings::Finding;
 use crate::flags;
 
 #[derive(Clone, Debug, PartialEq)]
 pub enum Type {
     Domain,
     Email,
+    Port,
+    Ip,
+    Asn,
+    Netblock,
+    Url,
 }
 
 impl fmt::Display for Type {
@@ -20,6 +28,21 @@ impl fmt::Display for Type {
             Type::Email => {
                 write!(formatter, "email")
             }
+            Type::Port => {
+                write!(formatter, "port")
+            }
+            Type::Ip => {
+                write!(formatter, "ip")
+            }
+            Type::Asn => {
+                write!(formatter, "asn")
+            }
+            Type::Netblock => {
+                write!(formatter, "netblock")
+            }
+            Type::Url => {
+                write!(formatter, "url")
+            }
         }
     }
 }
@@ -33,6 +56,27 @@ impl Serialize for Type {
     }
 }
 
+/// An address, the network it belongs to and the domains resolving to it
+#[derive(Clone, Debug, Default)]
+pub struct Host {
+    pub ip: String,
+    pub netblock: Option<String>,
+    pub asn: Option<String>,
+    pub organization: Option<String>,
+    pub domains: Vec<String>,
+}
+
+/// The grade given to the security headers of a web server
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct HeadersSummary {
+    pub url: String,
+    pub domain: String,
+    pub grade: String,
+    pub score: u8,
+    /// The amount of issues found in the headers and cookies
+    pub issues: usize,
+}
+
 #[derive(Clone, Debug, Serialize)]
 pub struct Node {
     r#type: Type,
@@ -96,6 +140,30 @@ impl Node {
         );
     }
 
+    pub fn add_finding(&mut self, finding: Finding) {
+        if !self.data.contains_key("findings") {
+            self.data
+                .insert(String::from("findings"), Value::Array(Vec::new()));
+        }
+        if let Some(Value::Array(findings)) = self.data.get_mut("findings")
+            && let Ok(finding) = serde_json::to_value(finding)
+        {
+            findings.push(finding);
+        }
+    }
+
+    pub fn get_findings(&self) -> Vec<Finding> {
+        self.get_data("findings")
+            .and_then(|findings| serde_json::from_value(findings.clone()).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn has_flag(&self, flag: usize) -> bool {
+        self.get_data("flags")
+            .and_then(|flags| flags.as_u64())
+            .is_some_and(|flags| flags::contains(flags as usize, flag))
+    }
+
     #[allow(dead_code)]
     pub fn get_or_init_map(&mut self, key: &str) -> serde_json::Map<String, Value> {
         if !self.data.contains_key(key) {
@@ -111,6 +179,51 @@ impl Node {
         self.connections.clone()
     }
 
+    /// Returns the first connection of the given type
+    pub fn get_connection(&self, r#type: Type) -> Option<&Node> {
+        self.connections
+            .iter()
+            .find(|connection| connection.r#type == r#type)
+    }
+
+    /// Returns the addresses the domain resolves to
+    pub fn get_ip_addrs(&self) -> Vec<IpAddr> {
+        self.connections
+            .iter()
+            .filter(|connection| connection.r#type == Type::Ip)
+            .filter_map(|connection| connection.value.parse().ok())
+            .collect()
+    }
+
+    /// Gathers the addresses the domains below this node resolve to, merging the ones shared by several domains
+    pub fn collect_hosts(&self, hosts: &mut BTreeMap<String, Host>) {
+        for connection in &self.connections {
+            if self.r#type == Type::Domain && connection.r#type == Type::Ip {
+                let host = hosts
+                    .entry(connection.value.clone())
+                    .or_insert_with(|| Host {
+                        ip: connection.value.clone(),
+                        netblock: connection
+                            .get_connection(Type::Netblock)
+                            .map(|netblock| netblock.value.clone()),
+                        asn: connection
+                            .get_connection(Type::Asn)
+                            .map(|asn| asn.value.clone()),
+                        organization: connection
+                            .get_connection(Type::Asn)
+                            .and_then(|asn| asn.get_data("organization"))
+                            .and_then(|organization| organization.as_str())
+                            .map(|organization| organization.to_string()),
+                        domains: Vec::new(),
+                    });
+                if !host.domains.contains(&self.value) {
+                    host.domains.push(self.value.clone());
+                }
+            }
+            connection.collect_hosts(hosts);
+        }
+    }
+
     pub fn find(&mut self, node: &Node) -> Option<&mut Node> {
         if self.equals(node) {
             return Some(self);
@@ -147,7 +260,7 @@ impl Node {
         let flags = if let Some(flags) = self.get_data("flags") {
             let mut result = String::from("#### Flags\n");
             result += format!(
-                "\n- `IS_RECENT` => {}\n- `HAS_EXPIRED` => {}\n- `POSSIBLE_TAKEOVER` => {}",
+                "\n- `IS_RECENT` => {}\n- `HAS_EXPIRED` => {}\n- `POSSIBLE_TAKEOVER` => {}\n- `WILDCARD` => {}\n- `ZONE_TRANSFER` => {}\n- `SPOOFABLE` => {}\n- `DELEGATION_TAKEOVER` => {}\n- `EXPIRING` => {}",
                 flags::contains_to_markdown(
                     flags.as_u64().unwrap() as usize,
                     flags::domain::IS_RECENT
@@ -169,7 +282,27 @@ impl Node {
                     )
                 } else {
                     "❌".to_string()
-                }
+                },
+                flags::contains_to_markdown(
+                    flags.as_u64().unwrap() as usize,
+                    flags::domain::WILDCARD
+                ),
+                flags::contains_to_markdown(
+                    flags.as_u64().unwrap() as usize,
+                    flags::domain::ZONE_TRANSFER
+                ),
+                flags::contains_to_markdown(
+                    flags.as_u64().unwrap() as usize,
+                    flags::domain::SPOOFABLE
+                ),
+                flags::contains_to_markdown(
+                    flags.as_u64().unwrap() as usize,
+                    flags::domain::DELEGATION_TAKEOVER
+                ),
+                flags::contains_to_markdown(
+                    flags.as_u64().unwrap() as usize,
+                    flags::domain::EXPIRING
+                )
             )
             .as_str();
             Some(result)
@@ -177,9 +310,367 @@ impl Node {
             None
         };
 
+        let addresses = self
+            .connections
+            .iter()
+            .filter(|conn| conn.r#type == Type::Ip)
+            .map(|ip| {
+                let mut details = Vec::new();
+                if let Some(asn) = ip.get_connection(Type::Asn) {
+                    details.push(
+                        match asn.get_data("organization").and_then(|o| o.as_str()) {
+                            Some(organization) => format!("{} {}", asn.value, organization),
+                            None => asn.value.clone(),
+                        },
+                    );
+                }
+                if let Some(country) = ip
+                    .get_data("geoinfo")
+                    .and_then(|geoinfo| geoinfo["country"].as_str())
+                {
+                    details.push(country.to_string());
+                }
+                if details.is_empty() {
+                    format!("\n- `{}`", ip.value)
+                } else {
+                    format!("\n- `{}` ({})", ip.value, details.join(", "))
+                }
+            })
+            .collect::<String>();
+        let addresses = (!addresses.is_empty()).then(|| format!("#### Addresses\n{}", addresses));
+
+        let dns = self
+            .get_data("dns")
+            .and_then(|dns| dns.as_object())
+            .filter(|dns| !dns.is_empty())
+            .map(|dns| {
+                let mut result =
+                    String::from("#### DNS Records\n\n| Type | Value |\n| --- | --- |");
+                for (record_type, values) in dns {
+                    for value in values.as_array().into_iter().flatten() {
+                        result += format!(
+                            "\n| {} | `{}` |",
+                            record_type,
+                            value.as_str().unwrap_or_default().replace('|', "\\|")
+                        )
+                        .as_str();
+                    }
+                }
+                result
+            });
+
+        let email = self.get_data("email").map(|email| {
+            let or_none = |value: Option<String>| value.unwrap_or(String::from("None"));
+            format!(
+                "#### Email Security\n\n- SPF: {}\n- DMARC: {}\n- DKIM: {}\n- MTA-STS: {}\n- TLS-RPT: {}\n- BIMI: {}",
+                or_none(email["spf"]["record"].as_str().map(|record| format!(
+                    "`{}` ({} DNS lookups)",
+                    record, email["spf"]["lookups"]
+                ))),
+                or_none(email["dmarc"]["record"].as_str().map(|record| {
+                    match email["dmarc"]["inherited_from"].as_str() {
+                        Some(domain) => format!("`{}` (Inherited from `{}`)", record, domain),
+                        None => format!("`{}`", record),
+                    }
+                })),
+                or_none(
+                    email["dkim"]
+                        .as_array()
+                        .filter(|dkim| !dkim.is_empty())
+                        .map(|dkim| dkim
+                            .iter()
+                            .map(|key| format!("`{}`", key["selector"].as_str().unwrap_or_default()))
+                            .collect::<Vec<String>>()
+                            .join(", "))
+                ),
+                or_none(
+                    email["mta_sts"]
+                        .as_object()
+                        .map(|mta_sts| format!(
+                            "`{}`",
+                            mta_sts["mode"].as_str().unwrap_or("unknown")
+                        ))
+                ),
+                if email["tls_rpt"].is_object() {
+                    "✅"
+                } else {
+                    "❌"
+                },
+                if email["bimi"].is_object() {
+                    "✅"
+                } else {
+                    "❌"
+                }
+            )
+        });
+
+        let whois = self.get_data("whois").map(|whois| {
+            let or_none = |value: &Value| {
+                value
+                    .as_str()
+                    .map(|value| format!("`{}`", value))
+                    .unwrap_or(String::from("None"))
+            };
+            let join = |values: &Value| {
+                values
+                    .as_array()
+                    .filter(|values| !values.is_empty())
+                    .map(|values| {
+                        values
+                            .iter()
+                            .map(|value| format!("`{}`", value.as_str().unwrap_or_default()))
+                            .collect::<Vec<String>>()
+                            .join(", ")
+                    })
+                    .unwrap_or(String::from("None"))
+            };
+            format!(
+                "#### Registration\n\n- Registrar: {}\n- Registrant: {}\n- Created: {}\n- Expires: {}\n- Nameservers: {}\n- Status: {}",
+                or_none(&whois["registrar"]),
+                or_none(&whois["registrant"]),
+                or_none(&whois["created"]),
+                or_none(&whois["expires"]),
+                join(&whois["nameservers"]),
+                join(&whois["status"])
+            )
+        });
+
+        let whois_dependencies = self
+            .get_data("whois_dependencies")
+            .and_then(|dependencies| dependencies.as_array())
+            .map(|dependencies| {
+                let mut result = String::from(
+                    "#### Dependencies\n\n| Domain | Record | Registrar | Expires |\n| --- | --- | --- | --- |",
+                );
+                for dependency in dependencies {
+                    result += format!(
+                        "\n| {} | {} | {} | {} |",
+                        dependency["domain"].as_str().unwrap_or_default(),
+                        dependency["record_type"].as_str().unwrap_or_default(),
+                        dependency["registrar"].as_str().unwrap_or_default(),
+                        dependency["expires"].as_str().unwrap_or_default()
+                    )
+                    .as_str();
+                }
+                result
+            });
+
+        let open_ports = self
+            .get_data("open_ports")
+            .and_then(|ports| ports.as_array())
+            .map(|ports| {
+                let mut result = String::from("#### Open Ports\n");
+                for port in ports {
+                    result += format!("\n- `{}`", port).as_str();
+                }
+                result
+            });
+
+        let cloud = [
+            ("Provider", "cloud_provider"),
+            ("Region", "region"),
+            ("Service", "service"),
+            ("CDN", "cdn"),
+            ("WAF", "waf"),
+        ]
+        .iter()
+        .filter(|_| self.r#type == Type::Domain)
+        .filter_map(|(label, key)| {
+            self.get_data(key)
+                .and_then(|value| value.as_str())
+                .map(|value| format!("\n- {}: `{}`", label, value))
+        })
+        .collect::<String>();
+        let cloud = (!cloud.is_empty()).then(|| format!("#### Cloud\n{}", cloud));
+
+        let service = self
+            .get_data("service")
+            .filter(|_| self.r#type == Type::Port)
+            .map(|service| {
+                let mut result = format!(
+                    "#### Service\n\n- Service: `{}`",
+                    service.as_str().unwrap_or_default()
+                );
+                if let Some(product) = self.get_data("product") {
+                    result +=
+                        format!("\n- Product: `{}`", product.as_str().unwrap_or_default()).as_str();
+                }
+                if let Some(version) = self.get_data("version") {
+                    result +=
+                        format!("\n- Version: `{}`", version.as_str().unwrap_or_default()).as_str();
+                }
+                result
+            });
+
+        let http = self
+            .get_data("http")
+            .and_then(|http| http.as_object())
+            .filter(|http| {
+                http.values()
+                    .any(|probe| probe["available"].as_bool().unwrap_or_default())
+            })
+            .map(|http| {
+                let mut result = String::from(
+                    "#### HTTP\n\n| URL | Status | Title | Server | Technologies |\n| --- | --- | --- | --- | --- |",
+                );
+                for (url, probe) in http
+                    .iter()
+                    .filter(|(_, probe)| probe["available"].as_bool().unwrap_or_default())
+                {
+                    let technologies = probe["technologies"]
+                        .as_array()
+                        .map(|technologies| {
+                            technologies
+                                .iter()
+                                .map(|technology| match technology["version"].as_str() {
+                                    Some(version) => format!(
+                                        "{} {}",
+                                        technology["name"].as_str().unwrap_or_default(),
+                                        version
+                                    ),
+                                    None => technology["name"]
+                                        .as_str()
+                                        .unwrap_or_default()
+                                        .to_string(),
+                                })
+                                .collect::<Vec<String>>()
+                                .join(", ")
+                        })
+                        .unwrap_or_default();
+                    result += format!(
+                        "\n| {} | {} | {} | {} | {} |",
+                        url,
+                        probe["status"],
+                        probe["title"].as_str().unwrap_or_default().replace('|', "\\|"),
+                        probe["server"].as_str().unwrap_or_default(),
+                        technologies
+                    )
+                    .as_str();
+                }
+                result
+            });
+
+        let urls = self
+            .connections
+            .iter()
+            .filter(|conn| conn.r#type == Type::Url)
+            .map(|url| {
+                format!(
+                    "\n| {} | {} | {} |",
+                    url.value,
+                    url.get_data("status")
+                        .map(|status| status.to_string())
+                        .unwrap_or_default(),
+                    url.get_data("size")
+                        .map(|size| size.to_string())
+                        .unwrap_or_default()
+                )
+            })
+            .collect::<String>();
+        let urls = (!urls.is_empty()).then(|| {
+            format!(
+                "#### URLs\n\n| URL | Status | Size |\n| --- | --- | --- |{}",
+                urls
+            )
+        });
+
+        let javascript = self
+            .get_data("javascript")
+            .and_then(|javascript| javascript.as_array())
+            .filter(|javascript| !javascript.is_empty())
+            .map(|javascript| {
+                let mut result = String::from(
+                    "#### JavaScript\n\n| Type | Value | Source |\n| --- | --- | --- |",
+                );
+                for record in javascript {
+                    result += format!(
+                        "\n| {} | `{}` | {}:{} |",
+                        record["type"].as_str().unwrap_or_default(),
+                        record["value"]
+                            .as_str()
+                            .unwrap_or_default()
+                            .replace('|', "\\|"),
+                        record["file"].as_str().unwrap_or_default(),
+                        record["line"]
+                    )
+                    .as_str();
+                }
+                result
+            });
+
+        let tls = self
+            .get_data("tls")
+            .and_then(|tls| tls.as_object())
+            .map(|tls| {
+                let mut result = String::from("#### TLS");
+                for (port, certificate) in tls {
+                    result += format!(
+                        "\n\n##### Port {}\n\n- Subject: `{}`\n- Issuer: `{}`\n- Valid: `{}` to `{}`\n- Key: `{} {}`\n- SANs: {}\n- Trusted: {}\n- Hostname match: {}",
+                        port,
+                        certificate["subject"].as_str().unwrap_or_default(),
+                        certificate["issuer"].as_str().unwrap_or_default(),
+                        certificate["not_before"].as_str().unwrap_or_default(),
+                        certificate["not_after"].as_str().unwrap_or_default(),
+                        certificate["key_type"].as_str().unwrap_or_default(),
+                        certificate["key_size"],
+                        certificate["sans"]
+                            .as_array()
+                            .map(|sans| sans
+                                .iter()
+                                .map(|san| format!("`{}`", san.as_str().unwrap_or_default()))
+                                .collect::<Vec<String>>()
+                                .join(", "))
+                            .unwrap_or_default(),
+                        if certificate["trusted"].as_bool().unwrap_or_default() {
+                            "✅"
+                        } else {
+                            "❌"
+                        },
+                        if certificate["hostname_match"].as_bool().unwrap_or_default() {
+                            "✅"
+                        } else {
+                            "❌"
+                        }
+                    )
+                    .as_str();
+                }
+                result
+            });
+
+        let mut findings = self.get_findings();
+        findings.sort_by_key(|finding| std::cmp::Reverse(finding.severity));
+        let findings = if findings.is_empty() {
+            None
+        } else {
+            let mut result = String::from(
+                "#### Findings\n\n| Severity | Module | Finding | Evidence |\n| --- | --- | --- | --- |",
+            );
+            for finding in findings {
+                result += format!(
+                    "\n| {} | {} | {} | {} |",
+                    finding.severity,
+                    finding.module,
+                    finding.title,
+                    finding
+                        .evidence
+                        .map(|evidence| format!("`{}`", evidence.replace('|', "\\|")))
+                        .unwrap_or_default()
+                )
+                .as_str();
+            }
+            Some(result)
+        };
+
+        // The infrastructure is rendered along with the addresses rather than as its own nodes
         let connections_markdown = self
-            .get_connections()
+            .connections
             .iter()
+            .filter(|conn| {
+                !matches!(
+                    conn.r#type,
+                    Type::Ip | Type::Asn | Type::Netblock | Type::Url
+                )
+            })
             .map(|conn| conn.to_markdown())
             .collect::<Vec<String>>()
             .join("\n\n");
@@ -188,6 +679,45 @@ impl Node {
         if let Some(flags) = flags {
             sections.push(flags);
         }
+        if let Some(addresses) = addresses {
+            sections.push(addresses);
+        }
+        if let Some(dns) = dns {
+            sections.push(dns);
+        }
+        if let Some(email) = email {
+            sections.push(email);
+        }
+        if let Some(whois) = whois {
+            sections.push(whois);
+        }
+        if let Some(whois_dependencies) = whois_dependencies {
+            sections.push(whois_dependencies);
+        }
+        if let Some(cloud) = cloud {
+            sections.push(cloud);
+        }
+        if let Some(open_ports) = open_ports {
+            sections.push(open_ports);
+        }
+        if let Some(service) = service {
+            sections.push(service);
+        }
+        if let Some(http) = http {
+            sections.push(http);
+        }
+        if let Some(urls) = urls {
+            sections.push(urls);
+        }
+        if let Some(javascript) = javascript {
+            sections.push(javascript);
+        }
+        if let Some(tls) = tls {
+            sections.push(tls);
+        }
+        if let Some(findings) = findings {
+            sections.push(findings);
+        }
         if !connections_markdown.is_empty() {
             sections.push(connections_markdown);
         }
@@ -1,6 +1,6 @@
 use crate::database::{
     Database,
-    node::{Node, Type},
+    node::{HeadersSummary, Node, Type},
 };
 
 use serde_json::Value;
@@ -84,3 +84,51 @@ fn search_node_root() {
             .is_none()
     );
 }
+
+#[test]
+fn group_hosts() {
+    let mut database = Database::new(Node::new(Type::Domain, String::from("krypton.ninja")));
+    let mut ip_node = Node::new(Type::Ip, String::from("192.0.2.1"));
+    let mut asn_node = Node::new(Type::Asn, String::from("AS64496"));
+    asn_node.add_data(String::from("organization"), Value::from("Krypton"));
+    ip_node.connect(asn_node);
+    let mut domain_node = Node::new(Type::Domain, String::from("go.krypton.ninja"));
+    domain_node.connect(ip_node.clone());
+    let root_node = database.get_root();
+    root_node.connect(ip_node);
+    root_node.connect(domain_node);
+    assert!(
+        database
+            .search(Type::Asn, String::from("AS64496"))
+            .is_some()
+    );
+
+    let hosts = database.get_hosts();
+    assert_eq!(hosts.len(), 1);
+    assert_eq!(hosts[0].organization.as_deref(), Some("Krypton"));
+    assert_eq!(hosts[0].domains, vec!["krypton.ninja", "go.krypton.ninja"]);
+}
+
+#[test]
+fn add_headers_summary() {
+    let node = Node::new(Type::Domain, String::from("krypton.ninja"));
+    let mut database = Database::new(node);
+    for (url, grade) in [
+        ("https://krypton.ninja", "C"),
+        ("https://go.krypton.ninja", "A"),
+        ("https://krypton.ninja", "B"),
+    ] {
+        database.add_headers_summary(HeadersSummary {
+            url: String::from(url),
+            domain: String::from("krypton.ninja"),
+            grade: String::from(grade),
+            score: 80,
+            issues: 3,
+        });
+    }
+
+    let summaries = database.get_headers_summaries();
+    assert_eq!(summaries.len(), 2);
+    assert_eq!(summaries[0].url, "https://go.krypton.ninja");
+    assert_eq!(summaries[1].grade, "B");
+}
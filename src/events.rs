@@ -9,6 +9,9 @@ pub enum Type {
     /// String: the domain
     /// usize: the port that is open
     OpenPort(String, usize),
+    /// String: the domain
+    /// String: the base URL of the live web server, e.g. `https://example.com:8443`
+    LiveHttpHost(String, String),
 }
 
 impl fmt::Display for Type {
@@ -26,6 +29,9 @@ impl fmt::Display for Type {
             Type::OpenPort(_, _) => {
                 write!(formatter, "open:port")
             }
+            Type::LiveHttpHost(_, _) => {
+                write!(formatter, "live:http_host")
+            }
         }
     }
 }
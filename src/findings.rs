@@ -0,0 +1,62 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Info,
+    Low,
+    Medium,
+    High,
+    Critical,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Severity::Info => {
+                write!(formatter, "info")
+            }
+            Severity::Low => {
+                write!(formatter, "low")
+            }
+            Severity::Medium => {
+                write!(formatter, "medium")
+            }
+            Severity::High => {
+                write!(formatter, "high")
+            }
+            Severity::Critical => {
+                write!(formatter, "critical")
+            }
+        }
+    }
+}
+
+/// An issue found by a module, saved on the node it concerns
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct Finding {
+    /// The name of the module that found the issue
+    pub module: String,
+    pub severity: Severity,
+    pub title: String,
+    /// What the finding is based on, e.g. a header value or a response snippet
+    pub evidence: Option<String>,
+}
+
+impl Finding {
+    pub fn new(
+        module: impl Into<String>,
+        severity: Severity,
+        title: impl Into<String>,
+        evidence: Option<String>,
+    ) -> Self {
+        Finding {
+            module: module.into(),
+            severity,
+            title: title.into(),
+            evidence,
+        }
+    }
+}
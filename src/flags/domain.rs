@@ -1,3 +1,8 @@
 pub const IS_RECENT: usize = 1 << 0;
 pub const HAS_EXPIRED: usize = 1 << 1;
 pub const POSSIBLE_TAKEOVER: usize = 1 << 2;
+pub const WILDCARD: usize = 1 << 3;
+pub const ZONE_TRANSFER: usize = 1 << 4;
+pub const SPOOFABLE: usize = 1 << 5;
+pub const DELEGATION_TAKEOVER: usize = 1 << 6;
+pub const EXPIRING: usize = 1 << 7;
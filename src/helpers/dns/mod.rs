@@ -0,0 +1,321 @@
+use std::fs;
+use std::io::{Read, Write};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, TcpStream, UdpSocket};
+use std::time::Duration;
+
+use hickory_proto::op::{Edns, Message, MessageType, OpCode, Query, ResponseCode};
+use hickory_proto::rr::{Name, RData, Record, RecordType};
+
+use crate::{config, logger};
+
+#[cfg(test)]
+mod tests;
+
+/// Used when no nameserver is configured and none can be read from `/etc/resolv.conf`
+const FALLBACK_NAMESERVERS: [&str; 2] = ["1.1.1.1", "8.8.8.8"];
+
+/// The second-level labels under which country code domains are registered, e.g. `co.uk`
+const SECOND_LEVEL_LABELS: [&str; 7] = ["ac", "co", "com", "edu", "gov", "net", "org"];
+
+/// A minimal blocking DNS client querying the configured nameservers directly
+pub struct Resolver {
+    nameservers: Vec<SocketAddr>,
+    timeout: Duration,
+    attempts: usize,
+}
+
+impl Resolver {
+    pub fn new(config: Option<&config::ResolverConfig>) -> Self {
+        let mut nameservers = config
+            .and_then(|config| config.nameservers.clone())
+            .unwrap_or_else(read_system_nameservers)
+            .iter()
+            .filter_map(|nameserver| match parse_nameserver(nameserver) {
+                Some(address) => Some(address),
+                None => {
+                    logger::error(
+                        "resolver",
+                        format!("Ignoring the invalid nameserver '{}'", nameserver),
+                    );
+                    None
+                }
+            })
+            .collect::<Vec<SocketAddr>>();
+        if nameservers.is_empty() {
+            nameservers = FALLBACK_NAMESERVERS
+                .iter()
+                .filter_map(|nameserver| parse_nameserver(nameserver))
+                .collect();
+        }
+        Resolver {
+            nameservers,
+            timeout: Duration::from_millis(
+                config.and_then(|config| config.timeout).unwrap_or(3000),
+            ),
+            attempts: config
+                .and_then(|config| config.attempts)
+                .unwrap_or(2)
+                .max(1),
+        }
+    }
+
+    /// Queries the configured nameservers in turn until one of them answers
+    pub fn query(&self, name: &str, record_type: RecordType) -> Result<Message, String> {
+        let mut error = String::from("No nameserver configured");
+        for _ in 0..self.attempts {
+            for nameserver in &self.nameservers {
+                match self.query_nameserver(*nameserver, name, record_type) {
+                    // Another nameserver might not be failing
+                    Ok(response) if response.response_code() == ResponseCode::ServFail => {
+                        error = format!("'{}' failed answering", nameserver);
+                    }
+                    Ok(response) => return Ok(response),
+                    Err(e) => error = e,
+                }
+            }
+        }
+        Err(error)
+    }
+
+    /// Queries a specific nameserver, over UDP first and then over TCP if the answer was truncated
+    pub fn query_nameserver(
+        &self,
+        nameserver: SocketAddr,
+        name: &str,
+        record_type: RecordType,
+    ) -> Result<Message, String> {
+        let request = build_request(name, record_type)?;
+        let bytes = request.to_vec().map_err(|e| e.to_string())?;
+
+        let local_address: SocketAddr = if nameserver.is_ipv4() {
+            (Ipv4Addr::UNSPECIFIED, 0).into()
+        } else {
+            (Ipv6Addr::UNSPECIFIED, 0).into()
+        };
+        let socket = UdpSocket::bind(local_address).map_err(|e| e.to_string())?;
+        socket
+            .set_read_timeout(Some(self.timeout))
+            .map_err(|e| e.to_string())?;
+        socket
+            .send_to(&bytes, nameserver)
+            .map_err(|e| e.to_string())?;
+
+        let mut buffer = [0u8; 4096];
+        loop {
+            let (read, source) = socket
+                .recv_from(&mut buffer)
+                .map_err(|e| format!("'{}' didn't answer: {}", nameserver, e))?;
+            if source != nameserver {
+                continue;
+            }
+            let Ok(response) = Message::from_vec(&buffer[..read]) else {
+                continue;
+            };
+            if response.id() != request.id() {
+                continue;
+            }
+            if response.truncated() {
+                return self.query_tcp(nameserver, &request);
+            }
+            return Ok(response);
+        }
+    }
+
+    fn query_tcp(&self, nameserver: SocketAddr, request: &Message) -> Result<Message, String> {
+        let mut stream = self.connect_tcp(nameserver)?;
+        send_tcp(&mut stream, request)?;
+        read_tcp(&mut stream)
+    }
+
+    pub fn connect_tcp(&self, nameserver: SocketAddr) -> Result<TcpStream, String> {
+        let stream =
+            TcpStream::connect_timeout(&nameserver, self.timeout).map_err(|e| e.to_string())?;
+        let _ = stream.set_read_timeout(Some(self.timeout));
+        let _ = stream.set_write_timeout(Some(self.timeout));
+        Ok(stream)
+    }
+
+    /// Returns the answers of the requested type as text, which is empty if there are none or the query failed
+    pub fn lookup(&self, name: &str, record_type: RecordType) -> Vec<String> {
+        match self.query(name, record_type) {
+            Ok(response) => get_answers(&response, record_type)
+                .iter()
+                .filter_map(|record| record.data().map(format_rdata))
+                .collect(),
+            Err(_) => Vec::new(),
+        }
+    }
+
+    /// Returns every IPv4 and IPv6 address of the domain
+    pub fn lookup_ip(&self, domain: &str) -> Vec<IpAddr> {
+        let mut ip_addrs = Vec::new();
+        for record_type in [RecordType::A, RecordType::AAAA] {
+            for address in self.lookup(domain, record_type) {
+                if let Ok(ip_addr) = address.parse::<IpAddr>()
+                    && !ip_addrs.contains(&ip_addr)
+                {
+                    ip_addrs.push(ip_addr);
+                }
+            }
+        }
+        ip_addrs
+    }
+}
+
+pub fn build_request(name: &str, record_type: RecordType) -> Result<Message, String> {
+    let name = Name::from_ascii(name).map_err(|e| e.to_string())?;
+    let mut request = Message::new();
+    request
+        .set_id(rand::random())
+        .set_message_type(MessageType::Query)
+        .set_op_code(OpCode::Query)
+        .set_recursion_desired(true)
+        .add_query(Query::query(name, record_type));
+    let mut edns = Edns::new();
+    edns.set_max_payload(4096);
+    request.set_edns(edns);
+    Ok(request)
+}
+
+/// Sends a message over TCP, prefixed by its length
+pub fn send_tcp(stream: &mut TcpStream, message: &Message) -> Result<(), String> {
+    let bytes = message.to_vec().map_err(|e| e.to_string())?;
+    let mut framed = (bytes.len() as u16).to_be_bytes().to_vec();
+    framed.extend(bytes);
+    stream.write_all(&framed).map_err(|e| e.to_string())
+}
+
+/// Reads a message sent over TCP, prefixed by its length
+pub fn read_tcp(stream: &mut TcpStream) -> Result<Message, String> {
+    let mut length = [0u8; 2];
+    stream.read_exact(&mut length).map_err(|e| e.to_string())?;
+    let mut bytes = vec![0u8; u16::from_be_bytes(length) as usize];
+    stream.read_exact(&mut bytes).map_err(|e| e.to_string())?;
+    Message::from_vec(&bytes).map_err(|e| e.to_string())
+}
+
+/// Returns the answers of the given type, skipping the CNAME records that may precede them
+pub fn get_answers(response: &Message, record_type: RecordType) -> Vec<&Record> {
+    response
+        .answers()
+        .iter()
+        .filter(|record| record.record_type() == record_type)
+        .collect()
+}
+
+/// Formats the data of a record without the trailing dots of the names it contains
+pub fn format_rdata(rdata: &RData) -> String {
+    match rdata {
+        RData::TXT(txt) => txt
+            .txt_data()
+            .iter()
+            .map(|data| String::from_utf8_lossy(data).to_string())
+            .collect::<Vec<String>>()
+            .join(""),
+        _ => rdata
+            .to_string()
+            .split(' ')
+            .map(|part| part.strip_suffix('.').unwrap_or(part))
+            .collect::<Vec<&str>>()
+            .join(" "),
+    }
+}
+
+/// Returns the domain a host would be registered under, approximated without the public suffix list
+pub fn get_registrable_domain(host: &str) -> String {
+    let labels = host.trim_end_matches('.').split('.').collect::<Vec<&str>>();
+    let length = if labels.len() >= 3
+        && labels[labels.len() - 1].len() == 2
+        && SECOND_LEVEL_LABELS.contains(&labels[labels.len() - 2])
+    {
+        3
+    } else {
+        2
+    };
+    labels[labels.len().saturating_sub(length)..].join(".")
+}
+
+/// Whether the label is a valid hostname label, underscores being allowed as in `_dmarc`
+pub fn is_valid_label(label: &str) -> bool {
+    !label.is_empty()
+        && label.len() <= 63
+        && !label.starts_with('-')
+        && !label.ends_with('-')
+        && label
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+/// Whether the character following a hostname continues it, e.g. `example.com.evil.com` for `example.com`
+pub fn continues_hostname(rest: &str) -> bool {
+    let mut chars = rest.chars();
+    match chars.next() {
+        Some(c) if c.is_ascii_alphanumeric() || c == '-' => true,
+        Some('.') => chars
+            .next()
+            .is_some_and(|c| c.is_ascii_alphanumeric() || c == '-'),
+        _ => false,
+    }
+}
+
+/// Returns the name queried for the PTR record of an address, e.g. `4.3.2.1.in-addr.arpa` for `1.2.3.4`
+pub fn get_reverse_name(ip_addr: IpAddr) -> String {
+    match ip_addr {
+        IpAddr::V4(ip_addr) => {
+            let octets = ip_addr.octets();
+            format!(
+                "{}.{}.{}.{}.in-addr.arpa",
+                octets[3], octets[2], octets[1], octets[0]
+            )
+        }
+        IpAddr::V6(ip_addr) => {
+            let nibbles = ip_addr
+                .octets()
+                .iter()
+                .rev()
+                .map(|octet| format!("{:x}.{:x}", octet & 0xf, octet >> 4))
+                .collect::<Vec<String>>();
+            format!("{}.ip6.arpa", nibbles.join("."))
+        }
+    }
+}
+
+/// Parses a nameserver given as an IP address, with an optional port
+pub fn parse_nameserver(nameserver: &str) -> Option<SocketAddr> {
+    if let Ok(address) = nameserver.parse::<SocketAddr>() {
+        return Some(address);
+    }
+    nameserver
+        .parse::<IpAddr>()
+        .ok()
+        .map(|ip_addr| SocketAddr::new(ip_addr, 53))
+}
+
+fn read_system_nameservers() -> Vec<String> {
+    fs::read_to_string("/etc/resolv.conf")
+        .map(|content| parse_resolv_conf(&content))
+        .unwrap_or_default()
+}
+
+/// Returns the nameservers listed in the content of a `resolv.conf` file
+pub fn parse_resolv_conf(content: &str) -> Vec<String> {
+    content
+        .lines()
+        .filter_map(|line| {
+            let mut parts = line.split_whitespace();
+            match (parts.next(), parts.next()) {
+                (Some("nameserver"), Some(nameserver)) => {
+                    // Drop the zone index of link-local IPv6 addresses
+                    Some(
+                        nameserver
+                            .split('%')
+                            .next()
+                            .unwrap_or(nameserver)
+                            .to_string(),
+                    )
+                }
+                _ => None,
+            }
+        })
+        .collect()
+}
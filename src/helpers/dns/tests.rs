@@ -0,0 +1,26 @@
+use crate::helpers::dns::{get_registrable_domain, parse_nameserver, parse_resolv_conf};
+
+#[test]
+fn get_apex_domains() {
+    assert_eq!(
+        get_registrable_domain("ns1.dns.krypton.ninja."),
+        "krypton.ninja"
+    );
+    assert_eq!(
+        get_registrable_domain("mail.krypton.co.uk"),
+        "krypton.co.uk"
+    );
+    assert_eq!(get_registrable_domain("ninja"), "ninja");
+}
+
+#[test]
+fn parse_nameservers() {
+    let content = "# Generated\nnameserver 127.0.0.53\nnameserver fe80::1%eth0\noptions edns0\n";
+    assert_eq!(parse_resolv_conf(content), vec!["127.0.0.53", "fe80::1"]);
+    assert_eq!(
+        parse_nameserver("127.0.0.1:5353"),
+        Some("127.0.0.1:5353".parse().unwrap())
+    );
+    assert_eq!(parse_nameserver("::1"), Some("[::1]:53".parse().unwrap()));
+    assert_eq!(parse_nameserver("localhost"), None);
+}
@@ -0,0 +1,53 @@
+use std::fs;
+
+use serde::de::DeserializeOwned;
+
+#[cfg(test)]
+mod tests;
+
+/// An entry of a file extending a built-in list, replacing the built-in entry with the same identifier
+pub trait Identified {
+    fn id(&self) -> &str;
+}
+
+/// Parses the content of an entries file, either a JSON array of entries or TOML tables named after the key, e.g. `[[paths]]`
+pub fn parse<T: DeserializeOwned>(
+    content: &str,
+    is_json: bool,
+    key: &str,
+) -> Result<Vec<T>, String> {
+    if is_json {
+        return serde_json::from_str(content)
+            .map_err(|e| format!("Failed to parse JSON {}: {}", key, e));
+    }
+    let mut table = toml::from_str::<toml::Table>(content)
+        .map_err(|e| format!("Failed to parse TOML {}: {}", key, e))?;
+    table
+        .remove(key)
+        .ok_or_else(|| format!("Failed to parse TOML {}: no `[[{}]]` table", key, key))?
+        .try_into()
+        .map_err(|e| format!("Failed to parse TOML {}: {}", key, e))
+}
+
+/// Reads an entries file, which is parsed as JSON when it has a `.json` extension and as TOML otherwise
+pub fn read<T: DeserializeOwned>(path: &str, key: &str) -> Result<Vec<T>, String> {
+    let content = fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read {} ({}): {}", key, path, e))?;
+    parse(&content, path.to_lowercase().ends_with(".json"), key)
+}
+
+/// Loads the embedded entries, extended with the ones of the given file
+pub fn load<T: DeserializeOwned + Identified>(
+    embedded: &str,
+    path: Option<&str>,
+    key: &str,
+) -> Result<Vec<T>, String> {
+    let mut entries = parse::<T>(embedded, false, key)?;
+    if let Some(path) = path {
+        for entry in read::<T>(path, key)? {
+            entries.retain(|existing| existing.id() != entry.id());
+            entries.push(entry);
+        }
+    }
+    Ok(entries)
+}
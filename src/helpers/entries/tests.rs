@@ -0,0 +1,30 @@
+use serde::Deserialize;
+
+use crate::helpers::entries::{Identified, parse};
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct Entry {
+    name: String,
+}
+
+impl Identified for Entry {
+    fn id(&self) -> &str {
+        &self.name
+    }
+}
+
+#[test]
+fn parse_entries() {
+    let expected = vec![Entry {
+        name: String::from("krypton"),
+    }];
+    assert_eq!(
+        parse::<Entry>("[[names]]\nname = \"krypton\"", false, "names").unwrap(),
+        expected
+    );
+    assert_eq!(
+        parse::<Entry>(r#"[{"name": "krypton"}]"#, true, "names").unwrap(),
+        expected
+    );
+    assert!(parse::<Entry>("[[other]]\nname = \"krypton\"", false, "names").is_err());
+}
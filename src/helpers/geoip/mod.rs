@@ -0,0 +1,132 @@
+use std::collections::HashMap;
+use std::net::IpAddr;
+use std::sync::Mutex;
+
+use ipnetwork::IpNetwork;
+use maxminddb::{Reader, geoip2};
+use serde::{Deserialize, Serialize};
+
+use crate::{config, logger};
+
+#[cfg(test)]
+mod tests;
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct GeoInfo {
+    pub city: Option<String>,
+    pub country: Option<String>,
+    /// The ISO 3166-1 alpha-2 code of the country
+    pub country_code: Option<String>,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+    /// The number of the autonomous system announcing the address
+    pub asn: Option<u32>,
+    /// The organization owning the autonomous system
+    pub as_organization: Option<String>,
+    /// The network announced by the autonomous system, e.g. `192.0.2.0/24`
+    pub network: Option<String>,
+}
+
+impl From<GeoInfo> for serde_json::Value {
+    fn from(value: GeoInfo) -> Self {
+        serde_json::Value::Object(serde_json::Map::from_iter([
+            (String::from("city"), value.city.into()),
+            (String::from("country"), value.country.into()),
+            (String::from("country_code"), value.country_code.into()),
+            (String::from("latitude"), value.latitude.into()),
+            (String::from("longitude"), value.longitude.into()),
+            (String::from("asn"), value.asn.into()),
+            (
+                String::from("as_organization"),
+                value.as_organization.into(),
+            ),
+            (String::from("network"), value.network.into()),
+        ]))
+    }
+}
+
+/// Looks up the location and the autonomous system of addresses in local MMDB files
+pub struct GeoIp {
+    city: Option<Reader<Vec<u8>>>,
+    asn: Option<Reader<Vec<u8>>>,
+    cache: Mutex<HashMap<IpAddr, Option<GeoInfo>>>,
+}
+
+fn open(path: Option<&String>) -> Option<Reader<Vec<u8>>> {
+    let path = path?;
+    match Reader::open_readfile(path) {
+        Ok(reader) => Some(reader),
+        Err(e) => {
+            logger::error(
+                "geoip",
+                format!("Failed to open the MMDB file ({}): {}", path, e),
+            );
+            None
+        }
+    }
+}
+
+/// Returns the network of the given prefix length the address belongs to
+pub fn get_network(ip_addr: IpAddr, prefix_len: usize) -> Result<IpNetwork, String> {
+    let prefix_len = u8::try_from(prefix_len).map_err(|e| e.to_string())?;
+    let network = IpNetwork::new(ip_addr, prefix_len).map_err(|e| e.to_string())?;
+    IpNetwork::new(network.network(), prefix_len).map_err(|e| e.to_string())
+}
+
+impl GeoIp {
+    pub fn new(config: Option<&config::GeoIpConfig>) -> Self {
+        GeoIp {
+            city: open(config.and_then(|config| config.city.as_ref())),
+            asn: open(config.and_then(|config| config.asn.as_ref())),
+            cache: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Returns the location and the autonomous system of the address, which is `None` when no file knows it
+    pub fn lookup(&self, ip_addr: IpAddr) -> Option<GeoInfo> {
+        if let Some(geoinfo) = self.cache.lock().unwrap().get(&ip_addr) {
+            return geoinfo.clone();
+        }
+        let mut geoinfo = GeoInfo::default();
+        let mut found = false;
+        if let Some(city) = self
+            .city
+            .as_ref()
+            .and_then(|reader| reader.lookup::<geoip2::City>(ip_addr).ok())
+        {
+            found = true;
+            let get_name = |names: Option<&std::collections::BTreeMap<&str, &str>>| {
+                names
+                    .and_then(|names| names.get("en"))
+                    .map(|name| name.to_string())
+            };
+            geoinfo.city = get_name(city.city.and_then(|city| city.names).as_ref());
+            if let Some(country) = city.country {
+                geoinfo.country = get_name(country.names.as_ref());
+                geoinfo.country_code = country.iso_code.map(|code| code.to_string());
+            }
+            if let Some(location) = city.location {
+                geoinfo.latitude = location.latitude;
+                geoinfo.longitude = location.longitude;
+            }
+        }
+        if let Some(asn) = self
+            .asn
+            .as_ref()
+            .and_then(|reader| reader.lookup_prefix::<geoip2::Asn>(ip_addr).ok())
+        {
+            let (asn, prefix_len) = asn;
+            found = true;
+            geoinfo.network = get_network(ip_addr, prefix_len)
+                .ok()
+                .map(|network| network.to_string());
+            geoinfo.asn = asn.autonomous_system_number;
+            geoinfo.as_organization = asn
+                .autonomous_system_organization
+                .map(|organization| organization.to_string());
+        }
+        let geoinfo = found.then_some(geoinfo);
+        self.cache.lock().unwrap().insert(ip_addr, geoinfo.clone());
+        geoinfo
+    }
+}
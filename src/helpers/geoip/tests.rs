@@ -0,0 +1,42 @@
+use std::net::IpAddr;
+
+use crate::helpers::geoip::{GeoInfo, get_network};
+
+#[test]
+fn get_networks() {
+    let ip_addr = "192.0.2.77".parse::<IpAddr>().unwrap();
+    assert_eq!(
+        get_network(ip_addr, 24).unwrap().to_string(),
+        "192.0.2.0/24"
+    );
+    assert_eq!(
+        get_network(ip_addr, 32).unwrap().to_string(),
+        "192.0.2.77/32"
+    );
+    assert_eq!(get_network(ip_addr, 0).unwrap().to_string(), "0.0.0.0/0");
+    assert!(get_network(ip_addr, 33).is_err());
+
+    let ip_addr = "2001:db8:1234::1".parse::<IpAddr>().unwrap();
+    assert_eq!(
+        get_network(ip_addr, 32).unwrap().to_string(),
+        "2001:db8::/32"
+    );
+    assert!(get_network(ip_addr, 129).is_err());
+}
+
+#[test]
+fn convert_to_json() {
+    let geoinfo = GeoInfo {
+        country_code: Some(String::from("CH")),
+        asn: Some(64496),
+        network: Some(String::from("192.0.2.0/24")),
+        ..Default::default()
+    };
+    let value = serde_json::Value::from(geoinfo.clone());
+    assert_eq!(value["country_code"], "CH");
+    assert_eq!(value["asn"], 64496);
+    assert_eq!(value["network"], "192.0.2.0/24");
+    assert!(value["city"].is_null());
+    // Every field is kept, as in the serialized form
+    assert_eq!(value, serde_json::to_value(geoinfo).unwrap());
+}
@@ -0,0 +1,25 @@
+use std::time::Duration;
+
+use reqwest::blocking::Client;
+use reqwest::redirect;
+
+/// The timeout of the requests, in seconds, when none is configured
+const DEFAULT_TIMEOUT: u64 = 10;
+
+/// Builds the client used by the modules, which doesn't follow redirects and accepts invalid certificates
+pub fn client(timeout: Option<u64>) -> Result<Client, String> {
+    client_with_policy(timeout, redirect::Policy::none())
+}
+
+/// Builds the client used by the modules with the given redirect policy
+pub fn client_with_policy(
+    timeout: Option<u64>,
+    policy: redirect::Policy,
+) -> Result<Client, String> {
+    Client::builder()
+        .redirect(policy)
+        .timeout(Duration::from_secs(timeout.unwrap_or(DEFAULT_TIMEOUT)))
+        .danger_accept_invalid_certs(true)
+        .build()
+        .map_err(|e| e.to_string())
+}
@@ -1,2 +1,9 @@
-pub mod network;
+pub mod dns;
+pub mod entries;
+pub mod geoip;
+pub mod http;
+pub mod parallel;
+pub mod signatures;
 pub mod ua;
+pub mod wildcard;
+pub mod wordlist;
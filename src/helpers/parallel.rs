@@ -0,0 +1,30 @@
+use std::{sync::Mutex, thread};
+
+/// Runs `task` on every item with at most `concurrency` items being processed at the same time, and collects the returned values
+pub fn filter_map<T, R, F>(items: Vec<T>, concurrency: usize, task: F) -> Vec<R>
+where
+    T: Send,
+    R: Send,
+    F: Fn(T) -> Option<R> + Sync,
+{
+    let queue = Mutex::new(items.into_iter());
+    let results = Mutex::new(Vec::new());
+    thread::scope(|scope| {
+        for _ in 0..concurrency.max(1) {
+            scope.spawn(|| {
+                loop {
+                    let item = queue.lock().unwrap().next();
+                    match item {
+                        Some(item) => {
+                            if let Some(result) = task(item) {
+                                results.lock().unwrap().push(result);
+                            }
+                        }
+                        None => break,
+                    }
+                }
+            });
+        }
+    });
+    results.into_inner().unwrap()
+}
@@ -0,0 +1,83 @@
+use std::collections::HashMap;
+use std::fs;
+
+use regex::{Captures, Regex};
+use serde::Deserialize;
+use serde::de::DeserializeOwned;
+
+use crate::helpers::entries;
+
+#[cfg(test)]
+mod tests;
+
+/// The header and cookie patterns of an entry of a signature file
+#[derive(Debug, Default, Deserialize)]
+pub struct HeaderPatterns {
+    /// Header names mapped to a pattern matching their value, an empty pattern only checks for the presence of the header
+    #[serde(default)]
+    headers: HashMap<String, String>,
+    /// Patterns matching the names of the cookies being set
+    #[serde(default)]
+    cookies: Vec<String>,
+}
+
+/// The compiled header and cookie patterns of a signature
+pub struct HeaderMatcher {
+    headers: Vec<(String, Regex)>,
+    cookies: Vec<Regex>,
+}
+
+impl HeaderMatcher {
+    pub fn new(patterns: &HeaderPatterns) -> Result<Self, String> {
+        Ok(HeaderMatcher {
+            headers: patterns
+                .headers
+                .iter()
+                .map(|(name, pattern)| Ok((name.to_lowercase(), compile(pattern)?)))
+                .collect::<Result<_, String>>()?,
+            cookies: compile_all(&patterns.cookies)?,
+        })
+    }
+
+    /// Returns the captures of the patterns matching the headers of a response, with lowercase names
+    pub fn captures<'a>(&self, headers: &'a [(String, String)]) -> Vec<Captures<'a>> {
+        let cookies = headers
+            .iter()
+            .filter(|(name, _)| name == "set-cookie")
+            .filter_map(|(_, value)| value.split_once('=').map(|(cookie, _)| cookie.trim()))
+            .collect::<Vec<&str>>();
+
+        let mut captures = Vec::new();
+        for (header, pattern) in &self.headers {
+            for (_, value) in headers.iter().filter(|(name, _)| name == header) {
+                captures.extend(pattern.captures(value));
+            }
+        }
+        for pattern in &self.cookies {
+            for cookie in &cookies {
+                captures.extend(pattern.captures(cookie));
+            }
+        }
+        captures
+    }
+}
+
+/// Compiles a pattern of a signature, which is case-insensitive
+pub fn compile(pattern: &str) -> Result<Regex, String> {
+    Regex::new(&format!("(?i){}", pattern))
+        .map_err(|e| format!("Invalid pattern '{}': {}", pattern, e))
+}
+
+pub fn compile_all(patterns: &[String]) -> Result<Vec<Regex>, String> {
+    patterns.iter().map(|pattern| compile(pattern)).collect()
+}
+
+/// Loads the signatures from the given JSON file, or the embedded ones if there's none
+pub fn load<T: DeserializeOwned>(embedded: &str, path: Option<&str>) -> Result<Vec<T>, String> {
+    let content = match path {
+        Some(path) => fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read signature file ({}): {}", path, e))?,
+        None => embedded.to_string(),
+    };
+    entries::parse(&content, true, "signatures")
+}
@@ -0,0 +1,30 @@
+use crate::helpers::signatures::{HeaderMatcher, HeaderPatterns, compile_all};
+
+fn get_matcher(content: &str) -> Result<HeaderMatcher, String> {
+    HeaderMatcher::new(&serde_json::from_str::<HeaderPatterns>(content).unwrap())
+}
+
+#[test]
+fn match_headers_and_cookies() {
+    let matcher = get_matcher(
+        r#"{ "headers": { "Server": "^nginx/?([0-9.]+)?", "cf-ray": "" }, "cookies": ["^PHPSESSID$"] }"#,
+    )
+    .unwrap();
+    let headers = [
+        ("server", "NGINX/1.18.0"),
+        ("set-cookie", "PHPSESSID=abc; path=/"),
+    ]
+    .iter()
+    .map(|(name, value)| (name.to_string(), value.to_string()))
+    .collect::<Vec<(String, String)>>();
+    let captures = matcher.captures(&headers);
+    assert_eq!(captures.len(), 2);
+    assert_eq!(captures[0].get(1).unwrap().as_str(), "1.18.0");
+    assert!(matcher.captures(&[]).is_empty());
+}
+
+#[test]
+fn reject_invalid_patterns() {
+    assert!(get_matcher(r#"{ "cookies": ["("] }"#).is_err());
+    assert!(compile_all(&[String::from("(")]).is_err());
+}
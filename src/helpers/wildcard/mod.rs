@@ -0,0 +1,65 @@
+use std::{collections::HashMap, net::IpAddr, sync::Mutex};
+
+use rand::{Rng, distr::Alphanumeric};
+
+#[cfg(test)]
+mod tests;
+
+/// Detects wildcard DNS records, e.g. `*.example.com`, by resolving random labels under a parent domain
+pub struct Wildcards {
+    probes: usize,
+    answers: Mutex<HashMap<String, Vec<IpAddr>>>,
+}
+
+impl Wildcards {
+    pub fn new(probes: usize) -> Self {
+        Wildcards {
+            probes,
+            answers: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Returns the addresses a wildcard record of the parent domain resolves to, which is empty if there's no wildcard
+    pub fn get_answers(&self, parent: &str, lookup: impl Fn(&str) -> Vec<IpAddr>) -> Vec<IpAddr> {
+        if let Some(answers) = self.answers.lock().unwrap().get(parent) {
+            return answers.clone();
+        }
+
+        let mut answers = Vec::new();
+        for _ in 0..self.probes {
+            let label = rand::rng()
+                .sample_iter(&Alphanumeric)
+                .take(16)
+                .map(char::from)
+                .collect::<String>()
+                .to_lowercase();
+            for ip_addr in lookup(&format!("{}.{}", label, parent)) {
+                if !answers.contains(&ip_addr) {
+                    answers.push(ip_addr);
+                }
+            }
+        }
+        self.answers
+            .lock()
+            .unwrap()
+            .insert(parent.to_string(), answers.clone());
+        answers
+    }
+
+    /// Whether the addresses of the domain are the ones of a wildcard record of its parent domain
+    pub fn matches(
+        &self,
+        domain: &str,
+        ip_addrs: &[IpAddr],
+        lookup: impl Fn(&str) -> Vec<IpAddr>,
+    ) -> bool {
+        let Some((_, parent)) = domain.split_once('.') else {
+            return false;
+        };
+        if ip_addrs.is_empty() || !parent.contains('.') {
+            return false;
+        }
+        let answers = self.get_answers(parent, lookup);
+        ip_addrs.iter().any(|ip_addr| answers.contains(ip_addr))
+    }
+}
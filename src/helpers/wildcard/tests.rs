@@ -0,0 +1,53 @@
+use std::net::IpAddr;
+
+use crate::helpers::wildcard::Wildcards;
+
+/// Resolves the names under `*.wildcard.example.com` like a wildcard record would
+fn lookup(name: &str) -> Vec<IpAddr> {
+    if name.ends_with(".wildcard.example.com") {
+        vec!["192.0.2.1".parse().unwrap(), "192.0.2.2".parse().unwrap()]
+    } else {
+        Vec::new()
+    }
+}
+
+#[test]
+fn match_wildcard_answers() {
+    let wildcards = Wildcards::new(2);
+    let ip_addr = "192.0.2.2".parse().unwrap();
+    assert!(wildcards.matches("www.wildcard.example.com", &[ip_addr], lookup));
+    // A single address of the wildcard record is enough
+    assert!(wildcards.matches(
+        "api.wildcard.example.com",
+        &["198.51.100.1".parse().unwrap(), ip_addr],
+        lookup
+    ));
+    assert!(!wildcards.matches(
+        "mail.wildcard.example.com",
+        &["198.51.100.1".parse().unwrap()],
+        lookup
+    ));
+    assert!(!wildcards.matches("www.example.com", &[ip_addr], lookup));
+}
+
+#[test]
+fn match_multi_level_domain() {
+    let wildcards = Wildcards::new(2);
+    let ip_addr = "192.0.2.1".parse().unwrap();
+    // Only the direct parent of the domain is probed
+    assert!(wildcards.matches("a.b.wildcard.example.com", &[ip_addr], lookup));
+    assert!(!wildcards.matches("a.b.c.example.com", &[ip_addr], lookup));
+}
+
+#[test]
+fn skip_unresolved_and_tld() {
+    let wildcards = Wildcards::new(2);
+    assert!(!wildcards.matches("www.wildcard.example.com", &[], lookup));
+    // Parents without a dot are top-level domains, which aren't probed
+    assert!(
+        !wildcards.matches("example.com", &["192.0.2.1".parse().unwrap()], |_| {
+            vec!["192.0.2.1".parse().unwrap()]
+        })
+    );
+    assert!(!wildcards.matches("localhost", &["127.0.0.1".parse().unwrap()], lookup));
+}
@@ -0,0 +1,13 @@
+use std::fs;
+
+/// Reads a wordlist file, skipping empty lines and `#` comments
+pub fn read(path: &str) -> Result<Vec<String>, String> {
+    let content = fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read wordlist ({}): {}", path, e))?;
+    Ok(content
+        .lines()
+        .map(|line| line.trim())
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| line.to_string())
+        .collect())
+}
@@ -12,6 +12,7 @@ mod config;
 mod database;
 mod debug;
 mod events;
+mod findings;
 mod flags;
 mod helpers;
 mod logger;
@@ -32,6 +33,9 @@ fn main() {
     if args.version {
         process::exit(0);
     }
+    if let Some(command) = &args.command {
+        process::exit(run_command(command));
+    }
     let config = match args.parse_config() {
         Ok(config) => config,
         Err(e) => {
@@ -48,3 +52,37 @@ fn main() {
         logger::error("session:run", err.to_string());
     }
 }
+
+/// Runs a subcommand and returns the exit code
+fn run_command(command: &args::Command) -> i32 {
+    match command {
+        args::Command::ValidateFingerprints { path } => {
+            let fingerprints = match helpers::entries::read::<
+                modules::domain_takeover::fingerprints::Fingerprint,
+            >(path, "fingerprints")
+            {
+                Ok(fingerprints) => fingerprints,
+                Err(e) => {
+                    logger::error("validate", e);
+                    return 1;
+                }
+            };
+            let problems = modules::domain_takeover::fingerprints::validate(&fingerprints);
+            for problem in problems.iter() {
+                logger::error("validate", problem);
+            }
+            if !problems.is_empty() {
+                return 1;
+            }
+            logger::info(
+                "validate",
+                format!(
+                    "'{}' is valid and has {} fingerprints",
+                    path,
+                    fingerprints.len()
+                ),
+            );
+            0
+        }
+    }
+}
@@ -0,0 +1,197 @@
+use std::sync::Mutex;
+
+use hickory_proto::rr::RecordType;
+use reqwest::blocking::Client;
+use reqwest::header::USER_AGENT;
+
+use crate::database::node::Type;
+use crate::modules::cloud_detect::ranges::Range;
+use crate::modules::cloud_detect::signatures::{Category, Signatures};
+use crate::modules::domain_takeover::get_cname_chain;
+use crate::modules::{Context, Module};
+use crate::session::Session;
+use crate::{config, events, helpers, logger};
+
+mod ranges;
+mod signatures;
+#[cfg(test)]
+mod tests;
+
+pub struct ModuleCloudDetect {
+    client: Client,
+    ranges: Vec<Range>,
+    signatures: Signatures,
+    processed_domains: Mutex<Vec<String>>,
+}
+
+impl ModuleCloudDetect {
+    pub fn new(config: config::CloudDetectConfig) -> Result<Self, String> {
+        let client = helpers::http::client(config.timeout)?;
+        let mut files = config
+            .ranges
+            .unwrap_or_default()
+            .into_iter()
+            .collect::<Vec<(String, String)>>();
+        files.sort();
+        let mut ranges = Vec::new();
+        for (provider, path) in files {
+            ranges.extend(ranges::load(&provider, &path)?);
+        }
+        Ok(ModuleCloudDetect {
+            client,
+            ranges,
+            signatures: Signatures::load(config.signatures.as_deref())?,
+            processed_domains: Mutex::new(Vec::new()),
+        })
+    }
+
+    pub fn process(&self, domain: String) {
+        self.processed_domains.lock().unwrap().push(domain)
+    }
+
+    pub fn has_processed(&self, domain: String) -> bool {
+        self.processed_domains.lock().unwrap().contains(&domain)
+    }
+
+    /// Fetches the headers, with lowercase names, served for the domain over HTTPS first and then over HTTP
+    fn fetch_headers(&self, domain: &str) -> Vec<(String, String)> {
+        for scheme in ["https", "http"] {
+            let response = self
+                .client
+                .get(format!("{}://{}", scheme, domain))
+                .header(USER_AGENT, helpers::ua::get_random())
+                .send();
+            if let Ok(response) = response {
+                return response
+                    .headers()
+                    .iter()
+                    .map(|(name, value)| {
+                        (
+                            name.as_str().to_lowercase(),
+                            String::from_utf8_lossy(value.as_bytes()).to_string(),
+                        )
+                    })
+                    .collect();
+            }
+        }
+        Vec::new()
+    }
+}
+
+impl Module for ModuleCloudDetect {
+    fn name(&self) -> String {
+        String::from("cloud:detect")
+    }
+
+    fn description(&self) -> String {
+        String::from(
+            "This module identifies the cloud provider hosting a domain and the CDN or WAF in front of it",
+        )
+    }
+
+    fn subscribers(&self) -> Vec<events::Type> {
+        vec![events::Type::DiscoveredDomain(String::new())]
+    }
+
+    fn execute(&self, session: &Session, context: Context) -> Result<(), String> {
+        let domain = match context {
+            Context::Domain(domain) => domain,
+            _ => {
+                return Err("Received wrong context, exiting module".to_string());
+            }
+        };
+
+        if self.has_processed(domain.to_string()) {
+            return Ok(());
+        }
+        self.process(domain.to_string());
+
+        let Some(ip_addrs) = session
+            .get_database()
+            .search(Type::Domain, domain.clone())
+            .map(|node| node.get_ip_addrs())
+        else {
+            return Ok(());
+        };
+        let range = ip_addrs
+            .iter()
+            .find_map(|ip_addr| ranges::find(&self.ranges, *ip_addr));
+
+        let cname_chain = session
+            .get_resolver()
+            .query(&domain, RecordType::A)
+            .map(|response| get_cname_chain(&response, &domain))
+            .unwrap_or_default();
+        let headers = if ip_addrs.is_empty() {
+            Vec::new()
+        } else {
+            self.fetch_headers(&domain)
+        };
+        let services = self.signatures.detect(&headers, &cname_chain);
+        let find_service = |category: Category| {
+            services
+                .iter()
+                .find(|service| service.categories.contains(&category))
+                .map(|service| service.name.clone())
+        };
+        let cdn = find_service(Category::Cdn);
+        let waf = find_service(Category::Waf);
+
+        if range.is_none() && cdn.is_none() && waf.is_none() {
+            return Ok(());
+        }
+
+        let mut details = Vec::new();
+        if let Some(range) = range {
+            let location = [&range.region, &range.service]
+                .into_iter()
+                .flatten()
+                .cloned()
+                .collect::<Vec<String>>();
+            if location.is_empty() {
+                details.push(format!("hosted on {}", range.provider));
+            } else {
+                details.push(format!(
+                    "hosted on {} ({})",
+                    range.provider,
+                    location.join(", ")
+                ));
+            }
+        }
+        if let Some(cdn) = &cdn {
+            details.push(format!("served through {}", cdn));
+        }
+        if let Some(waf) = &waf {
+            details.push(format!("protected by {}", waf));
+        }
+        logger::println(
+            self.name(),
+            format!("'{}' is {}", domain, details.join(", ")),
+        );
+
+        let mut database = session.get_database();
+        let Some(node) = database.search(Type::Domain, domain) else {
+            return Ok(());
+        };
+        if let Some(range) = range {
+            node.add_data(
+                String::from("cloud_provider"),
+                range.provider.clone().into(),
+            );
+            if let Some(region) = &range.region {
+                node.add_data(String::from("region"), region.clone().into());
+            }
+            if let Some(service) = &range.service {
+                node.add_data(String::from("service"), service.clone().into());
+            }
+        }
+        if let Some(cdn) = cdn {
+            node.add_data(String::from("cdn"), cdn.into());
+        }
+        if let Some(waf) = waf {
+            node.add_data(String::from("waf"), waf.into());
+        }
+
+        Ok(())
+    }
+}
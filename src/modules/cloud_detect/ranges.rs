@@ -0,0 +1,160 @@
+use std::fs;
+use std::net::IpAddr;
+
+use ipnetwork::IpNetwork;
+use serde_json::Value;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Range {
+    pub network: IpNetwork,
+    pub provider: String,
+    pub region: Option<String>,
+    pub service: Option<String>,
+}
+
+/// Reads the IP ranges a provider publishes
+pub fn load(provider: &str, path: &str) -> Result<Vec<Range>, String> {
+    let content = fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read IP ranges file ({}): {}", path, e))?;
+    let ranges = parse(provider, &content)
+        .map_err(|e| format!("Failed to parse IP ranges file ({}): {}", path, e))?;
+    if ranges.is_empty() {
+        return Err(format!("No IP ranges found in {}", path));
+    }
+    Ok(ranges)
+}
+
+/// Parses the IP ranges of a provider, either from one of the JSON formats the providers publish or from a CSV file with one range per line
+pub fn parse(provider: &str, content: &str) -> Result<Vec<Range>, String> {
+    let content = content.trim_start_matches('\u{feff}').trim_start();
+    if content.starts_with('{') || content.starts_with('[') {
+        let value: Value = serde_json::from_str(content).map_err(|e| e.to_string())?;
+        parse_json(provider, &value)
+    } else {
+        Ok(parse_csv(provider, content))
+    }
+}
+
+fn parse_json(provider: &str, value: &Value) -> Result<Vec<Range>, String> {
+    let get_string = |value: &Value| {
+        value
+            .as_str()
+            .filter(|value| !value.is_empty())
+            .map(|value| value.to_string())
+    };
+    let mut ranges = Vec::new();
+    let mut push = |prefix: &Value, region: Option<String>, service: Option<String>| {
+        if let Some(network) = prefix.as_str().and_then(|prefix| prefix.parse().ok()) {
+            ranges.push(Range {
+                network,
+                provider: provider.to_string(),
+                region,
+                service,
+            });
+        }
+    };
+
+    if value["prefixes"].is_array() {
+        // AWS (`ip-ranges.json`) and Google (`cloud.json` and `goog.json`)
+        let prefixes = value["prefixes"].as_array().into_iter().flatten();
+        let ipv6_prefixes = value["ipv6_prefixes"].as_array().into_iter().flatten();
+        for prefix in prefixes.chain(ipv6_prefixes) {
+            let network = ["ip_prefix", "ipv6_prefix", "ipv4Prefix", "ipv6Prefix"]
+                .iter()
+                .map(|key| &prefix[*key])
+                .find(|network| network.is_string())
+                .unwrap_or(&Value::Null);
+            let region = get_string(&prefix["region"]).or(get_string(&prefix["scope"]));
+            // AWS lists every range under `AMAZON` as well as under the service using it
+            let service = get_string(&prefix["service"]).filter(|service| service != "AMAZON");
+            push(network, region, service);
+        }
+    } else if value["values"].is_array() {
+        // Azure (`ServiceTags_Public.json`)
+        for tag in value["values"].as_array().into_iter().flatten() {
+            let properties = &tag["properties"];
+            for network in properties["addressPrefixes"]
+                .as_array()
+                .into_iter()
+                .flatten()
+            {
+                push(
+                    network,
+                    get_string(&properties["region"]),
+                    get_string(&properties["systemService"]),
+                );
+            }
+        }
+    } else if value["regions"].is_array() {
+        // Oracle Cloud (`public_ip_ranges.json`)
+        for region in value["regions"].as_array().into_iter().flatten() {
+            for cidr in region["cidrs"].as_array().into_iter().flatten() {
+                push(
+                    &cidr["cidr"],
+                    get_string(&region["region"]),
+                    get_string(&cidr["tags"][0]),
+                );
+            }
+        }
+    } else if value["addresses"].is_array() {
+        // Fastly (`public-ip-list`)
+        let addresses = value["addresses"].as_array().into_iter().flatten();
+        let ipv6_addresses = value["ipv6_addresses"].as_array().into_iter().flatten();
+        for network in addresses.chain(ipv6_addresses) {
+            push(network, None, None);
+        }
+    } else if let Some(networks) = value.as_array() {
+        for network in networks {
+            push(network, None, None);
+        }
+    } else {
+        return Err(String::from("Unknown format"));
+    }
+    Ok(ranges)
+}
+
+/// Parses a list of ranges, one per line, optionally followed by the columns of a geofeed, i.e. `prefix,country,region,city,postal code`
+fn parse_csv(provider: &str, content: &str) -> Vec<Range> {
+    let mut ranges = Vec::new();
+    for line in content.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let columns = line
+            .split(',')
+            .map(|column| column.trim().trim_matches('"'))
+            .collect::<Vec<&str>>();
+        // Skips the header line, if any
+        let Ok(network) = columns[0].parse() else {
+            continue;
+        };
+        let column = |index: usize| {
+            columns
+                .get(index)
+                .filter(|column| !column.is_empty())
+                .map(|column| column.to_string())
+        };
+        ranges.push(Range {
+            network,
+            provider: provider.to_string(),
+            region: column(2).or(column(1)),
+            service: None,
+        });
+    }
+    ranges
+}
+
+/// Finds the most specific range containing the address
+pub fn find(ranges: &[Range], ip_addr: IpAddr) -> Option<&Range> {
+    ranges
+        .iter()
+        .filter(|range| range.network.contains(ip_addr))
+        .max_by_key(|range| {
+            (
+                range.network.prefix(),
+                range.service.is_some(),
+                range.region.is_some(),
+            )
+        })
+}
@@ -0,0 +1,69 @@
+use regex::Regex;
+use serde::Deserialize;
+
+use crate::helpers::signatures::{self, HeaderMatcher, HeaderPatterns};
+
+const DEFAULT_SIGNATURES: &str = include_str!("signatures.json");
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Category {
+    Cdn,
+    Waf,
+}
+
+#[derive(Debug, Deserialize)]
+struct Signature {
+    name: String,
+    categories: Vec<Category>,
+    #[serde(flatten)]
+    patterns: HeaderPatterns,
+    /// Patterns matching the names the domain points at
+    #[serde(default)]
+    cnames: Vec<String>,
+}
+
+pub struct Service {
+    pub name: String,
+    pub categories: Vec<Category>,
+    matcher: HeaderMatcher,
+    cnames: Vec<Regex>,
+}
+
+pub struct Signatures {
+    services: Vec<Service>,
+}
+
+impl Signatures {
+    /// Loads the signatures from the given file, or the embedded ones if there's none
+    pub fn load(path: Option<&str>) -> Result<Self, String> {
+        Self::compile(signatures::load(DEFAULT_SIGNATURES, path)?)
+    }
+
+    fn compile(signatures: Vec<Signature>) -> Result<Self, String> {
+        let mut services = Vec::new();
+        for signature in signatures {
+            services.push(Service {
+                matcher: HeaderMatcher::new(&signature.patterns)?,
+                cnames: signatures::compile_all(&signature.cnames)?,
+                name: signature.name,
+                categories: signature.categories,
+            });
+        }
+        Ok(Signatures { services })
+    }
+
+    /// Detects the services in front of a domain from the headers of its response, with lowercase names, and the names it points at
+    pub fn detect(&self, headers: &[(String, String)], cname_chain: &[String]) -> Vec<&Service> {
+        self.services
+            .iter()
+            .filter(|service| {
+                !service.matcher.captures(headers).is_empty()
+                    || service
+                        .cnames
+                        .iter()
+                        .any(|pattern| cname_chain.iter().any(|name| pattern.is_match(name)))
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,94 @@
+use std::net::IpAddr;
+
+use crate::modules::cloud_detect::ranges::{self, Range};
+use crate::modules::cloud_detect::signatures::{Category, Signatures};
+
+fn find<'a>(ranges: &'a [Range], ip_addr: &str) -> Option<&'a Range> {
+    ranges::find(ranges, ip_addr.parse::<IpAddr>().unwrap())
+}
+
+#[test]
+fn parse_published_ranges() {
+    let aws = ranges::parse(
+        "AWS",
+        r#"{
+            "syncToken": "1",
+            "prefixes": [
+                { "ip_prefix": "3.0.0.0/8", "region": "us-east-1", "service": "AMAZON" },
+                { "ip_prefix": "3.5.0.0/16", "region": "us-east-1", "service": "EC2" }
+            ],
+            "ipv6_prefixes": [
+                { "ipv6_prefix": "2600:1f18::/33", "region": "us-east-1", "service": "EC2" }
+            ]
+        }"#,
+    )
+    .unwrap();
+    let range = find(&aws, "3.5.1.2").unwrap();
+    assert_eq!(range.service.as_deref(), Some("EC2"));
+    let range = find(&aws, "3.6.1.2").unwrap();
+    assert_eq!(range.region.as_deref(), Some("us-east-1"));
+    assert_eq!(range.service, None);
+    assert!(find(&aws, "2600:1f18::1").is_some());
+
+    let google = ranges::parse(
+        "Google Cloud",
+        r#"{ "prefixes": [{ "ipv4Prefix": "34.80.0.0/15", "service": "Google Cloud", "scope": "asia-east1" }] }"#,
+    )
+    .unwrap();
+    assert_eq!(
+        find(&google, "34.81.0.1").unwrap().region.as_deref(),
+        Some("asia-east1")
+    );
+
+    let azure = ranges::parse(
+        "Azure",
+        r#"{ "values": [{ "name": "AzureCloud.westeurope", "properties": { "region": "westeurope", "systemService": "", "addressPrefixes": ["13.69.0.0/17"] } }] }"#,
+    )
+    .unwrap();
+    let range = find(&azure, "13.69.1.1").unwrap();
+    assert_eq!(range.provider, "Azure");
+    assert_eq!(range.region.as_deref(), Some("westeurope"));
+    assert_eq!(range.service, None);
+
+    let csv = ranges::parse(
+        "DigitalOcean",
+        "# prefix,country,region,city\n104.131.0.0/18,US,US-NY,New York,10001\n173.245.48.0/20\n",
+    )
+    .unwrap();
+    assert_eq!(csv.len(), 2);
+    assert_eq!(
+        find(&csv, "104.131.1.1").unwrap().region.as_deref(),
+        Some("US-NY")
+    );
+    assert!(find(&csv, "173.245.48.1").unwrap().region.is_none());
+    assert!(find(&csv, "8.8.8.8").is_none());
+
+    assert!(ranges::parse("Unknown", r#"{ "ranges": [] }"#).is_err());
+}
+
+#[test]
+fn detect_cdn_and_waf() {
+    let signatures = Signatures::load(None).unwrap();
+    let headers = [
+        ("server", "cloudflare"),
+        ("set-cookie", "__cf_bm=abc; path=/"),
+    ]
+    .iter()
+    .map(|(name, value)| (name.to_string(), value.to_string()))
+    .collect::<Vec<(String, String)>>();
+    let services = signatures.detect(&headers, &[]);
+    assert_eq!(services.len(), 1);
+    assert_eq!(services[0].name, "Cloudflare");
+    assert!(services[0].categories.contains(&Category::Waf));
+
+    let services = signatures.detect(&[], &[String::from("d111111abcdef8.cloudfront.net")]);
+    assert_eq!(services.len(), 1);
+    assert_eq!(services[0].name, "Amazon CloudFront");
+    assert_eq!(services[0].categories, vec![Category::Cdn]);
+
+    assert!(
+        signatures
+            .detect(&[], &[String::from("krypton.github.io")])
+            .is_empty()
+    );
+}
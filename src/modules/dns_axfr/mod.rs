@@ -0,0 +1,235 @@
+use std::collections::BTreeMap;
+use std::net::SocketAddr;
+use std::sync::Mutex;
+
+use hickory_proto::op::ResponseCode;
+use hickory_proto::rr::{Record, RecordType};
+use serde_json::Value;
+
+use crate::database::node::Type;
+use crate::findings::{Finding, Severity};
+use crate::modules::{Context, Module};
+use crate::session::Session;
+use crate::{config, events, flags, helpers, logger};
+
+#[cfg(test)]
+mod tests;
+
+/// The maximum amount of records imported from a single transfer
+const MAX_RECORDS: usize = 100_000;
+
+/// The records of a zone grouped by their name, then by their type
+pub type Zone = BTreeMap<String, BTreeMap<String, Vec<String>>>;
+
+pub struct ModuleDNSAxfr {
+    config: config::DNSAxfrConfig,
+    processed_domains: Mutex<Vec<String>>,
+}
+
+impl ModuleDNSAxfr {
+    pub fn new(config: config::DNSAxfrConfig) -> Self {
+        ModuleDNSAxfr {
+            config,
+            processed_domains: Mutex::new(Vec::new()),
+        }
+    }
+
+    pub fn process(&self, domain: String) {
+        self.processed_domains.lock().unwrap().push(domain)
+    }
+
+    pub fn has_processed(&self, domain: String) -> bool {
+        self.processed_domains.lock().unwrap().contains(&domain)
+    }
+
+    /// Requests a transfer of the zone, which is complete once its SOA record has been sent a second time
+    fn transfer(
+        &self,
+        session: &Session,
+        nameserver: SocketAddr,
+        zone: &str,
+    ) -> Result<Vec<Record>, String> {
+        let resolver = session.get_resolver();
+        let mut stream = resolver.connect_tcp(nameserver)?;
+        let mut request = helpers::dns::build_request(zone, RecordType::AXFR)?;
+        request.set_recursion_desired(false);
+        helpers::dns::send_tcp(&mut stream, &request)?;
+
+        let mut records = Vec::new();
+        let mut soa_count = 0;
+        while soa_count < 2 && records.len() < MAX_RECORDS {
+            let response = helpers::dns::read_tcp(&mut stream)?;
+            if response.id() != request.id() {
+                continue;
+            }
+            if response.response_code() != ResponseCode::NoError {
+                return Err(response.response_code().to_string());
+            }
+            if response.answers().is_empty() {
+                return Err(String::from("Empty transfer"));
+            }
+            for record in response.answers() {
+                if record.record_type() == RecordType::SOA {
+                    soa_count += 1;
+                }
+                records.push(record.clone());
+            }
+        }
+        Ok(records)
+    }
+}
+
+/// Groups the transferred records by name, leaving out the closing SOA record
+pub fn group_records(records: &[Record]) -> Zone {
+    let mut zone = Zone::new();
+    let mut has_soa = false;
+    for record in records {
+        if record.record_type() == RecordType::SOA {
+            if has_soa {
+                continue;
+            }
+            has_soa = true;
+        }
+        let Some(rdata) = record.data() else {
+            continue;
+        };
+        let name = record.name().to_ascii();
+        let values = zone
+            .entry(name.trim_end_matches('.').to_lowercase())
+            .or_default()
+            .entry(record.record_type().to_string())
+            .or_default();
+        let value = helpers::dns::format_rdata(rdata);
+        if !values.contains(&value) {
+            values.push(value);
+        }
+    }
+    zone
+}
+
+impl Module for ModuleDNSAxfr {
+    fn name(&self) -> String {
+        String::from("dns:axfr")
+    }
+
+    fn description(&self) -> String {
+        String::from(
+            "This module attempts a zone transfer against every nameserver of a zone to retrieve all of its records",
+        )
+    }
+
+    fn subscribers(&self) -> Vec<events::Type> {
+        vec![events::Type::DiscoveredDomain(String::new())]
+    }
+
+    fn execute(&self, session: &Session, context: Context) -> Result<(), String> {
+        let domain = match context {
+            Context::Domain(domain) => domain,
+            _ => {
+                return Err("Received wrong context, exiting module".to_string());
+            }
+        };
+
+        if self.has_processed(domain.to_string()) {
+            return Ok(());
+        }
+        self.process(domain.to_string());
+
+        // Only the apex of a zone has its own SOA record
+        let resolver = session.get_resolver();
+        let is_apex = resolver
+            .query(&domain, RecordType::SOA)
+            .is_ok_and(|response| {
+                !helpers::dns::get_answers(&response, RecordType::SOA).is_empty()
+            });
+        if !is_apex {
+            return Ok(());
+        }
+
+        let port = self.config.port.unwrap_or(53);
+        let mut zone = Zone::new();
+        let mut vulnerable_nameservers = Vec::new();
+        for nameserver in resolver.lookup(&domain, RecordType::NS) {
+            for ip_addr in resolver.lookup_ip(&nameserver) {
+                match self.transfer(session, SocketAddr::new(ip_addr, port), &domain) {
+                    Ok(records) => {
+                        logger::println(
+                            self.name(),
+                            format!(
+                                "Transferred {} records of the '{}' zone from '{}' $[fg:red]$[effect:bold](Zone transfer allowed)",
+                                records.len(),
+                                domain,
+                                nameserver
+                            ),
+                        );
+                        zone.append(&mut group_records(&records));
+                        vulnerable_nameservers.push(nameserver.clone());
+                        break;
+                    }
+                    Err(e) => {
+                        if session.get_state().is_debug_or_verbose() {
+                            logger::info(
+                                self.name(),
+                                format!(
+                                    "'{}' ({}) refused the transfer of the '{}' zone: {}",
+                                    nameserver, ip_addr, domain, e
+                                ),
+                            );
+                        }
+                    }
+                }
+            }
+        }
+        if vulnerable_nameservers.is_empty() {
+            return Ok(());
+        }
+
+        if let Some(node) = session.get_database().search(Type::Domain, domain.clone()) {
+            node.add_flag(flags::domain::ZONE_TRANSFER);
+            node.add_data(
+                String::from("zone_transfer"),
+                Value::Array(
+                    vulnerable_nameservers
+                        .iter()
+                        .map(|nameserver| Value::String(nameserver.clone()))
+                        .collect(),
+                ),
+            );
+            node.add_finding(Finding::new(
+                self.name(),
+                Severity::High,
+                "Zone transfer (AXFR) is allowed",
+                Some(vulnerable_nameservers.join(", ")),
+            ));
+            // The records enumerated by `dns:records` are kept as they also include the SRV ones
+            if let Some(records) = zone.get(&domain)
+                && node.get_data("dns").is_none()
+            {
+                node.add_data(
+                    String::from("dns"),
+                    serde_json::to_value(records).map_err(|e| e.to_string())?,
+                );
+            }
+        }
+
+        for (name, records) in zone {
+            // Wildcard and service names aren't hosts
+            if name == domain
+                || name.starts_with('*')
+                || name.starts_with('_')
+                || !session.is_in_scope(&name)
+            {
+                continue;
+            }
+            let records = serde_json::to_value(records).map_err(|e| e.to_string())?;
+            session.discover_domain(&self.name(), name.clone(), Some(&domain));
+            if let Some(node) = session.get_database().search(Type::Domain, name)
+                && node.get_data("dns").is_none()
+            {
+                node.add_data(String::from("dns"), records);
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,38 @@
+use std::str::FromStr;
+
+use hickory_proto::rr::rdata::{A, MX, SOA};
+use hickory_proto::rr::{Name, RData, Record};
+
+use crate::modules::dns_axfr::group_records;
+
+fn record(name: &str, rdata: RData) -> Record {
+    Record::from_rdata(Name::from_str(name).unwrap(), 300, rdata)
+}
+
+#[test]
+fn group_transferred_records() {
+    let soa = RData::SOA(SOA::new(
+        Name::from_str("ns1.krypton.ninja.").unwrap(),
+        Name::from_str("hostmaster.krypton.ninja.").unwrap(),
+        1,
+        7200,
+        3600,
+        1209600,
+        300,
+    ));
+    let records = vec![
+        record("krypton.ninja.", soa.clone()),
+        record(
+            "krypton.ninja.",
+            RData::MX(MX::new(10, Name::from_str("mail.krypton.ninja.").unwrap())),
+        ),
+        record("go.krypton.ninja.", RData::A(A::new(127, 0, 0, 1))),
+        record("Go.Krypton.Ninja.", RData::A(A::new(127, 0, 0, 1))),
+        record("krypton.ninja.", soa),
+    ];
+    let zone = group_records(&records);
+    assert_eq!(zone.len(), 2);
+    assert_eq!(zone["krypton.ninja"]["SOA"].len(), 1);
+    assert_eq!(zone["krypton.ninja"]["MX"], vec!["10 mail.krypton.ninja"]);
+    assert_eq!(zone["go.krypton.ninja"]["A"], vec!["127.0.0.1"]);
+}
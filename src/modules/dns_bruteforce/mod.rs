@@ -0,0 +1,115 @@
+use std::sync::{Mutex, OnceLock};
+
+use crate::modules::{Context, Module};
+use crate::session::Session;
+use crate::{config, events, helpers};
+
+#[cfg(test)]
+mod tests;
+
+pub struct ModuleDNSBruteforce {
+    config: config::DNSBruteforceConfig,
+    words: OnceLock<Vec<String>>,
+    processed_domains: Mutex<Vec<String>>,
+}
+
+impl ModuleDNSBruteforce {
+    pub fn new(config: config::DNSBruteforceConfig) -> Self {
+        ModuleDNSBruteforce {
+            config,
+            words: OnceLock::new(),
+            processed_domains: Mutex::new(Vec::new()),
+        }
+    }
+
+    pub fn process(&self, domain: String) {
+        self.processed_domains.lock().unwrap().push(domain)
+    }
+
+    pub fn has_processed(&self, domain: String) -> bool {
+        self.processed_domains.lock().unwrap().contains(&domain)
+    }
+
+    fn get_words(&self, session: &Session) -> Result<&Vec<String>, String> {
+        if let Some(words) = self.words.get() {
+            return Ok(words);
+        }
+        let path = self
+            .config
+            .wordlist
+            .clone()
+            .unwrap_or(session.get_args().wordlist.clone());
+        let words = helpers::wordlist::read(&path)?
+            .into_iter()
+            .map(|word| word.to_lowercase())
+            .filter(|word| is_valid_word(word))
+            .collect::<Vec<String>>();
+        Ok(self.words.get_or_init(|| words))
+    }
+}
+
+/// Whether the word can be used as subdomain labels, wordlists commonly contain paths and file names as well
+pub fn is_valid_word(word: &str) -> bool {
+    word.split('.').all(helpers::dns::is_valid_label)
+}
+
+/// Returns the subdomains to resolve under the domain from the words of a wordlist
+pub fn get_candidates(words: &[String], domain: &str) -> Vec<String> {
+    words
+        .iter()
+        .map(|word| format!("{}.{}", word, domain))
+        .collect()
+}
+
+impl Module for ModuleDNSBruteforce {
+    fn name(&self) -> String {
+        String::from("dns:bruteforce")
+    }
+
+    fn description(&self) -> String {
+        String::from(
+            "This module will perform an active discovery of new domains by resolving the entries of a wordlist",
+        )
+    }
+
+    fn subscribers(&self) -> Vec<events::Type> {
+        vec![events::Type::DiscoveredDomain(String::new())]
+    }
+
+    fn execute(&self, session: &Session, context: Context) -> Result<(), String> {
+        let domain = match context {
+            Context::Domain(domain) => domain,
+            _ => {
+                return Err("Received wrong context, exiting module".to_string());
+            }
+        };
+
+        let recursion_depth = self.config.recursion_depth.unwrap_or(0);
+        let concurrency = self.config.concurrency.unwrap_or(50);
+        if self.has_processed(domain.to_string()) {
+            return Ok(());
+        }
+        self.process(domain.to_string());
+
+        // Only go as deep as configured from the target domain
+        let depth = domain
+            .split('.')
+            .count()
+            .saturating_sub(session.get_args().domain.split('.').count());
+        if depth > recursion_depth {
+            return Ok(());
+        }
+
+        let candidates = get_candidates(self.get_words(session)?, &domain);
+        let hits = helpers::parallel::filter_map(candidates, concurrency, |candidate| {
+            let ip_addrs = session.get_resolver().lookup_ip(&candidate);
+            (!ip_addrs.is_empty()).then_some((candidate, ip_addrs))
+        });
+
+        for (subdomain, ip_addrs) in hits {
+            session.discover_resolved_domain(&self.name(), subdomain, ip_addrs, Some(&domain));
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,35 @@
+use crate::modules::dns_bruteforce::{get_candidates, is_valid_word};
+
+#[test]
+fn filter_words() {
+    for word in ["www", "api-v2", "_dmarc", "dev.api", "mail01"] {
+        assert!(is_valid_word(word), "{}", word);
+    }
+    for word in [
+        "",
+        "-api",
+        "api-",
+        ".api",
+        "api.",
+        "dev..api",
+        "admin/login",
+        "index.php?id=1",
+        "my file",
+        &"a".repeat(64),
+    ] {
+        assert!(!is_valid_word(word), "{}", word);
+    }
+}
+
+#[test]
+fn build_candidates() {
+    let words = ["www", "dev.api"]
+        .iter()
+        .map(|word| word.to_string())
+        .collect::<Vec<String>>();
+    assert_eq!(
+        get_candidates(&words, "krypton.ninja"),
+        vec!["www.krypton.ninja", "dev.api.krypton.ninja"]
+    );
+    assert!(get_candidates(&[], "krypton.ninja").is_empty());
+}
@@ -0,0 +1,160 @@
+use std::sync::{Mutex, OnceLock};
+
+use crate::modules::{Context, Module};
+use crate::session::Session;
+use crate::{config, events, helpers, logger};
+
+mod permutations;
+#[cfg(test)]
+mod tests;
+
+/// The maximum amount of words learned from the discovered domains
+const MAX_LEARNED_WORDS: usize = 100;
+
+pub struct ModuleDNSPermutations {
+    config: config::DNSPermutationsConfig,
+    words: OnceLock<Vec<String>>,
+    learned_words: Mutex<Vec<String>>,
+    processed_domains: Mutex<Vec<String>>,
+    /// The domains discovered by this module, which aren't permuted again to keep runs bounded
+    permuted_domains: Mutex<Vec<String>>,
+}
+
+impl ModuleDNSPermutations {
+    pub fn new(config: config::DNSPermutationsConfig) -> Self {
+        ModuleDNSPermutations {
+            config,
+            words: OnceLock::new(),
+            learned_words: Mutex::new(Vec::new()),
+            processed_domains: Mutex::new(Vec::new()),
+            permuted_domains: Mutex::new(Vec::new()),
+        }
+    }
+
+    pub fn process(&self, domain: String) {
+        self.processed_domains.lock().unwrap().push(domain)
+    }
+
+    pub fn has_processed(&self, domain: String) -> bool {
+        self.processed_domains.lock().unwrap().contains(&domain)
+    }
+
+    fn get_words(&self) -> Result<&Vec<String>, String> {
+        if let Some(words) = self.words.get() {
+            return Ok(words);
+        }
+        let words = match &self.config.wordlist {
+            Some(path) => helpers::wordlist::read(path)?
+                .into_iter()
+                .map(|word| word.to_lowercase())
+                .filter(|word| helpers::dns::is_valid_label(word))
+                .collect(),
+            None => permutations::DEFAULT_WORDS
+                .iter()
+                .map(|word| word.to_string())
+                .collect(),
+        };
+        Ok(self.words.get_or_init(|| words))
+    }
+
+    /// Remembers the words the labels of a discovered domain are made of, and returns every word known so far
+    fn learn(&self, labels: &[&str]) -> Result<Vec<String>, String> {
+        let mut learned_words = self.learned_words.lock().unwrap();
+        for word in labels
+            .iter()
+            .flat_map(|label| permutations::get_words(label))
+        {
+            if learned_words.len() < MAX_LEARNED_WORDS && !learned_words.contains(&word) {
+                learned_words.push(word);
+            }
+        }
+
+        let mut words = self.get_words()?.clone();
+        for word in learned_words.iter() {
+            if !words.contains(word) {
+                words.push(word.clone());
+            }
+        }
+        Ok(words)
+    }
+}
+
+impl Module for ModuleDNSPermutations {
+    fn name(&self) -> String {
+        String::from("dns:permutations")
+    }
+
+    fn description(&self) -> String {
+        String::from(
+            "This module will discover new domains by resolving variations of the names of the discovered ones",
+        )
+    }
+
+    fn subscribers(&self) -> Vec<events::Type> {
+        vec![events::Type::DiscoveredDomain(String::new())]
+    }
+
+    fn execute(&self, session: &Session, context: Context) -> Result<(), String> {
+        let domain = match context {
+            Context::Domain(domain) => domain,
+            _ => {
+                return Err("Received wrong context, exiting module".to_string());
+            }
+        };
+
+        if self.has_processed(domain.to_string()) {
+            return Ok(());
+        }
+        self.process(domain.to_string());
+
+        // Only the labels below the target domain can be permuted
+        let target = &session.get_args().domain;
+        let Some(relative) = domain.strip_suffix(&format!(".{}", target)) else {
+            return Ok(());
+        };
+        let labels = relative.split('.').collect::<Vec<&str>>();
+        let words = self.learn(&labels)?;
+        if self.permuted_domains.lock().unwrap().contains(&domain)
+            || !helpers::dns::is_valid_label(labels[0])
+        {
+            return Ok(());
+        }
+
+        let Some((label, parent)) = domain.split_once('.') else {
+            return Ok(());
+        };
+        let max_candidates = self.config.max_candidates.unwrap_or(1000);
+        let concurrency = self.config.concurrency.unwrap_or(50);
+        let candidates = permutations::generate(label, parent, &words, max_candidates)
+            .into_iter()
+            .filter(|candidate| !session.get_state().has_discovered_domain(candidate.clone()))
+            .collect::<Vec<String>>();
+        if session.get_state().is_debug_or_verbose() {
+            logger::info(
+                self.name(),
+                format!(
+                    "Resolving {} permutations of '{}'",
+                    candidates.len(),
+                    domain
+                ),
+            );
+        }
+        let hits = helpers::parallel::filter_map(candidates, concurrency, |candidate| {
+            let ip_addrs = session.get_resolver().lookup_ip(&candidate);
+            // Every permutation would be a hit under a wildcard record
+            if ip_addrs.is_empty() || session.matches_wildcard(&candidate, &ip_addrs) {
+                return None;
+            }
+            Some((candidate, ip_addrs))
+        });
+
+        for (subdomain, ip_addrs) in hits {
+            // Permutations are usually siblings of the permuted domain rather than children
+            if session.discover_resolved_domain(&self.name(), subdomain.clone(), ip_addrs, None) {
+                self.permuted_domains.lock().unwrap().push(subdomain);
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,152 @@
+use std::collections::HashSet;
+
+use crate::helpers::dns;
+
+/// The words inserted and swapped when no wordlist is configured, mostly environments and common services
+pub const DEFAULT_WORDS: [&str; 48] = [
+    "admin",
+    "api",
+    "app",
+    "auth",
+    "backup",
+    "beta",
+    "cdn",
+    "ci",
+    "corp",
+    "demo",
+    "dev",
+    "develop",
+    "development",
+    "docs",
+    "external",
+    "gateway",
+    "git",
+    "int",
+    "internal",
+    "intranet",
+    "legacy",
+    "mail",
+    "mgmt",
+    "new",
+    "old",
+    "portal",
+    "preprod",
+    "private",
+    "prod",
+    "production",
+    "proxy",
+    "public",
+    "qa",
+    "sandbox",
+    "secure",
+    "stage",
+    "staging",
+    "static",
+    "stg",
+    "test",
+    "testing",
+    "uat",
+    "v1",
+    "v2",
+    "vpn",
+    "web",
+    "www",
+    "www2",
+];
+
+/// Splits a label into the words it's made of, e.g. `api-staging2` gives `api` and `staging`
+pub fn get_words(label: &str) -> Vec<String> {
+    label
+        .split(['-', '_'])
+        .map(|word| word.trim_end_matches(|c: char| c.is_ascii_digit()))
+        .filter(|word| word.len() > 1 && !word.chars().all(|c| c.is_ascii_digit()))
+        .map(|word| word.to_string())
+        .collect()
+}
+
+/// Returns the label with its numbers shifted by the given offset, keeping their zero padding
+fn shift_numbers(label: &str, offset: i64) -> Option<String> {
+    let mut result = String::new();
+    let mut digits = String::new();
+    let mut changed = false;
+    for c in label.chars().chain(std::iter::once(' ')) {
+        if c.is_ascii_digit() {
+            digits.push(c);
+            continue;
+        }
+        if !digits.is_empty() {
+            let number = digits.parse::<i64>().ok()? + offset;
+            if number < 0 {
+                return None;
+            }
+            result += &format!("{:0width$}", number, width = digits.len());
+            digits.clear();
+            changed = true;
+        }
+        if c != ' ' {
+            result.push(c);
+        }
+    }
+    changed.then_some(result)
+}
+
+/// Generates variations of the first label of a domain, under the same parent, with at most `max` of them
+pub fn generate(label: &str, parent: &str, words: &[String], max: usize) -> Vec<String> {
+    let mut labels = Vec::new();
+
+    // Neighbouring numbers, or the first ones when the label has none
+    let shifted = [-2, -1, 1, 2]
+        .iter()
+        .filter_map(|offset| shift_numbers(label, *offset))
+        .collect::<Vec<String>>();
+    if shifted.is_empty() {
+        labels.extend((1..=3).map(|number| format!("{}{}", label, number)));
+        labels.extend((1..=3).map(|number| format!("{}-{}", label, number)));
+    } else {
+        labels.extend(shifted);
+    }
+
+    // Swapping each word of the label with another one
+    let parts = label.split('-').collect::<Vec<&str>>();
+    if parts.len() > 1 {
+        for (index, part) in parts.iter().enumerate() {
+            for word in words {
+                if word == part {
+                    continue;
+                }
+                let mut swapped = parts.clone();
+                swapped[index] = word;
+                labels.push(swapped.join("-"));
+            }
+        }
+    }
+
+    // Prefixing and suffixing the label with another word
+    for word in words {
+        if word != label {
+            labels.push(format!("{}-{}", word, label));
+            labels.push(format!("{}-{}", label, word));
+        }
+    }
+
+    let mut candidates = Vec::new();
+    for label in labels {
+        if dns::is_valid_label(&label) {
+            candidates.push(format!("{}.{}", label, parent));
+        }
+    }
+    // Joining with dots adds a level, either above or below the label
+    for word in words {
+        if word != label {
+            candidates.push(format!("{}.{}.{}", word, label, parent));
+            candidates.push(format!("{}.{}.{}", label, word, parent));
+        }
+    }
+
+    let mut seen = HashSet::new();
+    candidates
+        .into_iter()
+        .filter(|candidate| seen.insert(candidate.clone()))
+        .take(max)
+        .collect()
+}
@@ -0,0 +1,34 @@
+use crate::modules::dns_permutations::permutations::{generate, get_words};
+
+fn words(words: &[&str]) -> Vec<String> {
+    words.iter().map(|word| word.to_string()).collect()
+}
+
+#[test]
+fn split_label_words() {
+    assert_eq!(get_words("api-staging2"), vec!["api", "staging"]);
+    assert_eq!(get_words("a-01-dev_eu"), vec!["dev", "eu"]);
+}
+
+#[test]
+fn generate_permutations() {
+    let candidates = generate("api-staging02", "krypton.ninja", &words(&["dev"]), 100);
+    assert!(candidates.contains(&String::from("api-staging01.krypton.ninja")));
+    assert!(candidates.contains(&String::from("api-staging03.krypton.ninja")));
+    assert!(candidates.contains(&String::from("dev-staging02.krypton.ninja")));
+    assert!(candidates.contains(&String::from("api-dev.krypton.ninja")));
+    assert!(candidates.contains(&String::from("dev-api-staging02.krypton.ninja")));
+    assert!(candidates.contains(&String::from("dev.api-staging02.krypton.ninja")));
+    assert!(!candidates.contains(&String::from("api-staging02.krypton.ninja")));
+
+    let candidates = generate("api", "krypton.ninja", &words(&["dev", "api"]), 4);
+    assert_eq!(
+        candidates,
+        vec![
+            "api1.krypton.ninja",
+            "api2.krypton.ninja",
+            "api3.krypton.ninja",
+            "api-1.krypton.ninja"
+        ]
+    );
+}
@@ -0,0 +1,181 @@
+use std::sync::Mutex;
+
+use hickory_proto::rr::RecordType;
+use serde_json::Value;
+
+use crate::database::node::Type;
+use crate::modules::{Context, Module};
+use crate::session::Session;
+use crate::{config, events, helpers, logger};
+
+#[cfg(test)]
+mod tests;
+
+const RECORD_TYPES: [RecordType; 8] = [
+    RecordType::A,
+    RecordType::AAAA,
+    RecordType::CNAME,
+    RecordType::MX,
+    RecordType::NS,
+    RecordType::TXT,
+    RecordType::SOA,
+    RecordType::CAA,
+];
+
+/// The service names probed for SRV records when none are configured
+const DEFAULT_SRV_NAMES: [&str; 16] = [
+    "_autodiscover._tcp",
+    "_caldav._tcp",
+    "_caldavs._tcp",
+    "_carddav._tcp",
+    "_carddavs._tcp",
+    "_imap._tcp",
+    "_imaps._tcp",
+    "_kerberos._tcp",
+    "_ldap._tcp",
+    "_matrix._tcp",
+    "_sip._tcp",
+    "_sip._udp",
+    "_sips._tcp",
+    "_submission._tcp",
+    "_xmpp-client._tcp",
+    "_xmpp-server._tcp",
+];
+
+pub struct ModuleDNSRecords {
+    config: config::DNSRecordsConfig,
+    processed_domains: Mutex<Vec<String>>,
+}
+
+impl ModuleDNSRecords {
+    pub fn new(config: config::DNSRecordsConfig) -> Self {
+        ModuleDNSRecords {
+            config,
+            processed_domains: Mutex::new(Vec::new()),
+        }
+    }
+
+    pub fn process(&self, domain: String) {
+        self.processed_domains.lock().unwrap().push(domain)
+    }
+
+    pub fn has_processed(&self, domain: String) -> bool {
+        self.processed_domains.lock().unwrap().contains(&domain)
+    }
+}
+
+/// Returns the domain name a record points to, for the record types that point to one
+pub fn get_target(record_type: &str, value: &str) -> Option<String> {
+    let target = match record_type {
+        "CNAME" | "NS" => value,
+        // The name comes after the preference for MX records, and after the priority, weight and port for SRV records
+        "MX" | "SRV" => value.split_whitespace().last()?,
+        _ => return None,
+    };
+    let target = target.trim_end_matches('.').to_lowercase();
+    (!target.is_empty()).then_some(target)
+}
+
+impl Module for ModuleDNSRecords {
+    fn name(&self) -> String {
+        String::from("dns:records")
+    }
+
+    fn description(&self) -> String {
+        String::from(
+            "This module enumerates the DNS records of a domain and discovers the domains they point to",
+        )
+    }
+
+    fn subscribers(&self) -> Vec<events::Type> {
+        vec![events::Type::DiscoveredDomain(String::new())]
+    }
+
+    fn execute(&self, session: &Session, context: Context) -> Result<(), String> {
+        let domain = match context {
+            Context::Domain(domain) => domain,
+            _ => {
+                return Err("Received wrong context, exiting module".to_string());
+            }
+        };
+
+        if self.has_processed(domain.to_string()) {
+            return Ok(());
+        }
+        self.process(domain.to_string());
+
+        let resolver = session.get_resolver();
+        let mut records = serde_json::Map::new();
+        for record_type in RECORD_TYPES {
+            let values = resolver.lookup(&domain, record_type);
+            if !values.is_empty() {
+                records.insert(
+                    record_type.to_string(),
+                    Value::Array(values.into_iter().map(Value::String).collect()),
+                );
+            }
+        }
+
+        let srv_names = self.config.srv.clone().unwrap_or_else(|| {
+            DEFAULT_SRV_NAMES
+                .iter()
+                .map(|name| name.to_string())
+                .collect()
+        });
+        let concurrency = self.config.concurrency.unwrap_or(10);
+        let srv_records = helpers::parallel::filter_map(srv_names, concurrency, |name| {
+            let values = resolver.lookup(&format!("{}.{}", name, domain), RecordType::SRV);
+            (!values.is_empty()).then(|| {
+                values
+                    .into_iter()
+                    .map(|value| format!("{} {}", name, value))
+                    .collect::<Vec<String>>()
+            })
+        });
+        if !srv_records.is_empty() {
+            let mut srv_records = srv_records.concat();
+            srv_records.sort();
+            records.insert(
+                String::from("SRV"),
+                Value::Array(srv_records.into_iter().map(Value::String).collect()),
+            );
+        }
+
+        if records.is_empty() {
+            return Ok(());
+        }
+        logger::println(
+            self.name(),
+            format!(
+                "Found {} records for '{}'",
+                records
+                    .values()
+                    .map(|values| values.as_array().map(Vec::len).unwrap_or_default())
+                    .sum::<usize>(),
+                domain
+            ),
+        );
+
+        let mut targets = Vec::new();
+        for (record_type, values) in &records {
+            for value in values.as_array().into_iter().flatten() {
+                if let Some(target) = get_target(record_type, value.as_str().unwrap_or_default())
+                    && !targets.contains(&target)
+                {
+                    targets.push(target);
+                }
+            }
+        }
+        if let Some(node) = session.get_database().search(Type::Domain, domain.clone()) {
+            node.add_data(String::from("dns"), Value::Object(records));
+        }
+
+        for target in targets {
+            if target != domain && session.is_in_scope(&target) {
+                session.discover_domain(&self.name(), target, Some(&domain));
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,18 @@
+use crate::modules::dns_records::get_target;
+
+#[test]
+fn get_record_targets() {
+    assert_eq!(
+        get_target("MX", "10 mail.krypton.ninja"),
+        Some(String::from("mail.krypton.ninja"))
+    );
+    assert_eq!(
+        get_target("SRV", "_sip._tcp 10 5 5060 SIP.krypton.ninja."),
+        Some(String::from("sip.krypton.ninja"))
+    );
+    assert_eq!(
+        get_target("CNAME", "krypton.github.io"),
+        Some(String::from("krypton.github.io"))
+    );
+    assert_eq!(get_target("TXT", "v=spf1 -all"), None);
+}
@@ -0,0 +1,175 @@
+use std::net::IpAddr;
+use std::sync::Mutex;
+
+use hickory_proto::rr::RecordType;
+use ipnetwork::Ipv4Network;
+
+use crate::database::node::Type;
+use crate::modules::{Context, Module};
+use crate::session::Session;
+use crate::{config, events, helpers, logger};
+
+#[cfg(test)]
+mod tests;
+
+pub struct ModuleDNSReverse {
+    config: config::DNSReverseConfig,
+    processed_ips: Mutex<Vec<IpAddr>>,
+    processed_networks: Mutex<Vec<Ipv4Network>>,
+}
+
+impl ModuleDNSReverse {
+    pub fn new(config: config::DNSReverseConfig) -> Self {
+        ModuleDNSReverse {
+            config,
+            processed_ips: Mutex::new(Vec::new()),
+            processed_networks: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Keeps the addresses that haven't been looked up yet, and marks them as looked up
+    fn process_ips(&self, ip_addrs: Vec<IpAddr>) -> Vec<IpAddr> {
+        let mut processed_ips = self.processed_ips.lock().unwrap();
+        let ip_addrs = ip_addrs
+            .into_iter()
+            .filter(|ip_addr| !processed_ips.contains(ip_addr))
+            .collect::<Vec<IpAddr>>();
+        processed_ips.extend(ip_addrs.iter().cloned());
+        ip_addrs
+    }
+
+    /// Whether the network wasn't swept yet, and marks it as swept
+    fn process_network(&self, network: Ipv4Network) -> bool {
+        let mut processed_networks = self.processed_networks.lock().unwrap();
+        if processed_networks.contains(&network) {
+            return false;
+        }
+        processed_networks.push(network);
+        true
+    }
+
+    /// Looks up the PTR records of the addresses, returning the names found for each address having any
+    fn lookup(&self, session: &Session, ip_addrs: Vec<IpAddr>) -> Vec<(IpAddr, Vec<String>)> {
+        let concurrency = self.config.concurrency.unwrap_or(20);
+        helpers::parallel::filter_map(ip_addrs, concurrency, |ip_addr| {
+            let names = session
+                .get_resolver()
+                .lookup(&helpers::dns::get_reverse_name(ip_addr), RecordType::PTR)
+                .iter()
+                .map(|name| name.trim_end_matches('.').to_lowercase())
+                .collect::<Vec<String>>();
+            (!names.is_empty()).then_some((ip_addr, names))
+        })
+    }
+
+    /// Whether the autonomous system of the address belongs to one of the configured organizations
+    fn belongs_to_organization(&self, session: &Session, ip_addr: IpAddr) -> bool {
+        let organizations = self.config.organizations.as_deref().unwrap_or_default();
+        if organizations.is_empty() {
+            return false;
+        }
+        let mut database = session.get_database();
+        let Some(organization) = database
+            .search(Type::Ip, ip_addr.to_string())
+            .and_then(|ip| ip.get_connection(Type::Asn))
+            .and_then(|asn| asn.get_data("organization"))
+            .and_then(|organization| organization.as_str())
+            .map(|organization| organization.to_lowercase())
+        else {
+            return false;
+        };
+        organizations
+            .iter()
+            .any(|expected| organization.contains(&expected.to_lowercase()))
+    }
+}
+
+/// Returns every address of the network of the given prefix length the address belongs to
+pub fn get_neighbors(ip_addr: IpAddr, prefix_len: u8) -> Vec<IpAddr> {
+    let IpAddr::V4(ip_addr) = ip_addr else {
+        return Vec::new();
+    };
+    Ipv4Network::new(ip_addr, prefix_len)
+        .map(|network| network.iter().map(IpAddr::V4).collect())
+        .unwrap_or_default()
+}
+
+impl Module for ModuleDNSReverse {
+    fn name(&self) -> String {
+        String::from("dns:reverse")
+    }
+
+    fn description(&self) -> String {
+        String::from(
+            "This module will look up the PTR records of the discovered addresses, and optionally of their neighbors, to find new domains",
+        )
+    }
+
+    fn subscribers(&self) -> Vec<events::Type> {
+        vec![events::Type::DiscoveredDomain(String::new())]
+    }
+
+    fn execute(&self, session: &Session, context: Context) -> Result<(), String> {
+        let domain = match context {
+            Context::Domain(domain) => domain,
+            _ => {
+                return Err("Received wrong context, exiting module".to_string());
+            }
+        };
+
+        let Some(ip_addrs) = session
+            .get_database()
+            .search(Type::Domain, domain.clone())
+            .map(|node| node.get_ip_addrs())
+        else {
+            return Ok(());
+        };
+        let ip_addrs = self.process_ips(ip_addrs);
+        if ip_addrs.is_empty() {
+            return Ok(());
+        }
+
+        let mut names = Vec::new();
+        let mut networks = Vec::new();
+        let prefix_len = self.config.prefix.unwrap_or(24).clamp(24, 32);
+        for (ip_addr, ptr_names) in self.lookup(session, ip_addrs) {
+            let IpAddr::V4(ipv4_addr) = ip_addr else {
+                names.extend(ptr_names);
+                continue;
+            };
+            // Only sweep the networks of the target, a name of the target on a shared hosting or CDN address isn't enough
+            if self.config.sweep.unwrap_or(false)
+                && self.belongs_to_organization(session, ip_addr)
+                && let Ok(network) = Ipv4Network::new(ipv4_addr, prefix_len)
+                && let Ok(network) = Ipv4Network::new(network.network(), prefix_len)
+                && self.process_network(network)
+            {
+                networks.push(network);
+            }
+            names.extend(ptr_names);
+        }
+
+        for network in networks {
+            if session.get_state().is_debug_or_verbose() {
+                logger::info(
+                    self.name(),
+                    format!("Sweeping the PTR records of {}", network),
+                );
+            }
+            let neighbors = self.process_ips(get_neighbors(IpAddr::V4(network.ip()), prefix_len));
+            for (_, ptr_names) in self.lookup(session, neighbors) {
+                names.extend(ptr_names);
+            }
+        }
+
+        names.sort();
+        names.dedup();
+        for name in names {
+            if session.is_in_scope(&name) {
+                session.discover_domain(&self.name(), name, None);
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,27 @@
+use std::net::IpAddr;
+
+use crate::helpers::dns::get_reverse_name;
+use crate::modules::dns_reverse::get_neighbors;
+
+#[test]
+fn format_reverse_name() {
+    assert_eq!(
+        get_reverse_name("192.0.2.10".parse().unwrap()),
+        "10.2.0.192.in-addr.arpa"
+    );
+    assert_eq!(
+        get_reverse_name("2001:db8::567:89ab".parse().unwrap()),
+        "b.a.9.8.7.6.5.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.8.b.d.0.1.0.0.2.ip6.arpa"
+    );
+}
+
+#[test]
+fn list_neighbors() {
+    let neighbors = get_neighbors("192.0.2.10".parse().unwrap(), 24);
+    assert_eq!(neighbors.len(), 256);
+    assert_eq!(neighbors[0], "192.0.2.0".parse::<IpAddr>().unwrap());
+    assert_eq!(neighbors[255], "192.0.2.255".parse::<IpAddr>().unwrap());
+
+    assert_eq!(get_neighbors("192.0.2.10".parse().unwrap(), 30).len(), 4);
+    assert!(get_neighbors("2001:db8::1".parse().unwrap(), 24).is_empty());
+}
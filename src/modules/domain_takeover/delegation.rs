@@ -0,0 +1,102 @@
+use std::net::SocketAddr;
+
+use hickory_proto::op::{Message, ResponseCode};
+use hickory_proto::rr::RecordType;
+use serde::Serialize;
+
+use crate::findings::Severity;
+use crate::helpers::dns::{Resolver, get_registrable_domain};
+
+/// An NS or MX record pointing at something that doesn't exist or doesn't answer for the domain
+#[derive(Debug, Serialize)]
+pub struct Issue {
+    pub record_type: String,
+    pub target: String,
+    pub reason: String,
+    #[serde(skip)]
+    pub severity: Severity,
+    /// Whether someone else could answer for the domain or receive its emails
+    pub takeover: bool,
+}
+
+/// Whether a nameserver refuses or fails answering for a zone it's supposed to serve
+pub fn is_lame(response: &Message) -> bool {
+    matches!(
+        response.response_code(),
+        ResponseCode::Refused | ResponseCode::ServFail
+    )
+}
+
+/// Checks why a host doesn't resolve, which is only claimable when its domain isn't registered
+fn check_unresolvable(resolver: &Resolver, host: &str) -> (String, bool) {
+    let registrable_domain = get_registrable_domain(host);
+    match resolver.query(&registrable_domain, RecordType::SOA) {
+        Ok(response) if response.response_code() == ResponseCode::NXDomain => (
+            format!(
+                "The domain '{}' isn't registered and could be registered by anyone",
+                registrable_domain
+            ),
+            true,
+        ),
+        _ => (String::from("The host doesn't resolve"), false),
+    }
+}
+
+pub fn check_nameserver(resolver: &Resolver, domain: &str, nameserver: &str) -> Option<Issue> {
+    let ip_addrs = resolver.lookup_ip(nameserver);
+    if ip_addrs.is_empty() {
+        let (reason, takeover) = check_unresolvable(resolver, nameserver);
+        return Some(Issue {
+            record_type: String::from("NS"),
+            target: nameserver.to_string(),
+            reason,
+            severity: if takeover {
+                Severity::High
+            } else {
+                Severity::Medium
+            },
+            takeover,
+        });
+    }
+
+    // Nameservers that don't answer at all are more likely filtered than lame
+    let responses = ip_addrs
+        .iter()
+        .filter_map(|ip_addr| {
+            resolver
+                .query_nameserver(SocketAddr::new(*ip_addr, 53), domain, RecordType::SOA)
+                .ok()
+        })
+        .collect::<Vec<Message>>();
+    if responses.is_empty() || !responses.iter().all(is_lame) {
+        return None;
+    }
+    Some(Issue {
+        record_type: String::from("NS"),
+        target: nameserver.to_string(),
+        reason: format!(
+            "The nameserver doesn't serve the zone ({}), which might be claimable on its provider",
+            responses[0].response_code()
+        ),
+        severity: Severity::High,
+        takeover: true,
+    })
+}
+
+pub fn check_mail_server(resolver: &Resolver, mail_server: &str) -> Option<Issue> {
+    if !resolver.lookup_ip(mail_server).is_empty() {
+        return None;
+    }
+    let (reason, takeover) = check_unresolvable(resolver, mail_server);
+    Some(Issue {
+        record_type: String::from("MX"),
+        target: mail_server.to_string(),
+        reason,
+        severity: if takeover {
+            Severity::High
+        } else {
+            Severity::Low
+        },
+        takeover,
+    })
+}
@@ -0,0 +1,206 @@
+use std::collections::HashSet;
+use std::fmt;
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::helpers::entries;
+
+/// The fingerprints used when no file is configured
+const DEFAULT_FINGERPRINTS: &str = include_str!("fingerprints.toml");
+
+/// Whether the resources of a platform can be claimed, as listed by can-i-take-over-xyz
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Status {
+    #[default]
+    #[serde(alias = "Vulnerable")]
+    Vulnerable,
+    /// Only under some conditions, e.g. with a specific plan or region
+    #[serde(alias = "Edge case")]
+    EdgeCase,
+    #[serde(alias = "Not vulnerable")]
+    NotVulnerable,
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Status::Vulnerable => {
+                write!(formatter, "vulnerable")
+            }
+            Status::EdgeCase => {
+                write!(formatter, "edge-case")
+            }
+            Status::NotVulnerable => {
+                write!(formatter, "not-vulnerable")
+            }
+        }
+    }
+}
+
+/// An entry of a fingerprints file
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Fingerprint {
+    pub service: String,
+    #[serde(default)]
+    pub status: Status,
+    /// Patterns found in the names of the CNAME chain pointing at the platform
+    #[serde(default)]
+    pub cname: Vec<String>,
+    /// Snippets of the content served for unclaimed resources
+    #[serde(default)]
+    pub fingerprint: Vec<String>,
+    /// Regular expressions matching the content served for unclaimed resources
+    #[serde(default)]
+    pub regex: Vec<String>,
+    /// The status codes served for unclaimed resources
+    #[serde(default)]
+    pub http_status: Vec<u16>,
+    /// Whether a CNAME target that doesn't exist is enough to claim the resource
+    #[serde(default)]
+    pub nxdomain: bool,
+}
+
+/// A platform whose resources can be claimed by anyone once they're deleted
+#[derive(Clone, Debug)]
+pub struct Provider {
+    pub name: String,
+    pub status: Status,
+    pub cnames: Vec<String>,
+    pub fingerprints: Vec<String>,
+    pub regexes: Vec<Regex>,
+    pub http_status: Vec<u16>,
+    pub nxdomain: bool,
+}
+
+impl Provider {
+    /// Whether one of the names of the CNAME chain points at the platform
+    pub fn matches_cname(&self, chain: &[String]) -> bool {
+        chain.iter().any(|name| {
+            self.cnames
+                .iter()
+                .any(|cname| name.contains(cname.as_str()))
+        })
+    }
+
+    /// Whether the platform has matchers for the content served
+    pub fn has_content_matchers(&self) -> bool {
+        !self.fingerprints.is_empty() || !self.regexes.is_empty()
+    }
+
+    /// Whether the response is the one served for unclaimed resources, every configured matcher having to match
+    pub fn matches_response(&self, status: u16, content: &str) -> bool {
+        if !self.has_content_matchers() && self.http_status.is_empty() {
+            return false;
+        }
+        let content_matches = !self.has_content_matchers()
+            || self
+                .fingerprints
+                .iter()
+                .any(|fingerprint| content.contains(fingerprint.as_str()))
+            || self.regexes.iter().any(|regex| regex.is_match(content));
+        content_matches && (self.http_status.is_empty() || self.http_status.contains(&status))
+    }
+}
+
+/// Returns the problems of the entries, which would otherwise never or always match
+pub fn validate(fingerprints: &[Fingerprint]) -> Vec<String> {
+    let mut problems = Vec::new();
+    let mut services = HashSet::new();
+    for (index, fingerprint) in fingerprints.iter().enumerate() {
+        let service = &fingerprint.service;
+        if service.trim().is_empty() {
+            problems.push(format!("Entry #{} has no service name", index + 1));
+            continue;
+        }
+        if !services.insert(service.to_lowercase()) {
+            problems.push(format!("'{}' is defined more than once", service));
+        }
+        if fingerprint
+            .cname
+            .iter()
+            .any(|cname| cname.trim().is_empty())
+        {
+            problems.push(format!("'{}' has an empty CNAME pattern", service));
+        }
+        if fingerprint
+            .fingerprint
+            .iter()
+            .any(|fingerprint| fingerprint.is_empty())
+        {
+            problems.push(format!("'{}' has an empty fingerprint", service));
+        }
+        for regex in fingerprint.regex.iter() {
+            if let Err(e) = Regex::new(regex) {
+                problems.push(format!(
+                    "'{}' has an invalid regex '{}': {}",
+                    service, regex, e
+                ));
+            }
+        }
+        for status in fingerprint.http_status.iter() {
+            if !(100..=599).contains(status) {
+                problems.push(format!(
+                    "'{}' has an invalid status code {}",
+                    service, status
+                ));
+            }
+        }
+        // Neither the NXDOMAIN nor the content matchers are checked for domains that don't point at the platform
+        if fingerprint.status != Status::NotVulnerable && fingerprint.cname.is_empty() {
+            problems.push(format!("'{}' has no CNAME pattern", service));
+        }
+        if fingerprint.status != Status::NotVulnerable
+            && !fingerprint.nxdomain
+            && fingerprint.fingerprint.is_empty()
+            && fingerprint.regex.is_empty()
+            && fingerprint.http_status.is_empty()
+        {
+            problems.push(format!(
+                "'{}' has nothing to match the CNAME target or the content against",
+                service
+            ));
+        }
+    }
+    problems
+}
+
+/// Validates the entries and compiles them into providers
+pub fn compile(fingerprints: Vec<Fingerprint>) -> Result<Vec<Provider>, String> {
+    let problems = validate(&fingerprints);
+    if !problems.is_empty() {
+        return Err(format!("Invalid fingerprints: {}", problems.join(", ")));
+    }
+    fingerprints
+        .into_iter()
+        .map(|fingerprint| {
+            Ok(Provider {
+                regexes: fingerprint
+                    .regex
+                    .iter()
+                    .map(|regex| Regex::new(regex).map_err(|e| e.to_string()))
+                    .collect::<Result<Vec<Regex>, String>>()?,
+                name: fingerprint.service,
+                status: fingerprint.status,
+                cnames: fingerprint
+                    .cname
+                    .iter()
+                    .map(|cname| cname.to_lowercase())
+                    .collect(),
+                fingerprints: fingerprint.fingerprint,
+                http_status: fingerprint.http_status,
+                nxdomain: fingerprint.nxdomain,
+            })
+        })
+        .collect()
+}
+
+/// Loads the providers from the given fingerprints file, or from the embedded one
+pub fn load(path: Option<&str>) -> Result<Vec<Provider>, String> {
+    let fingerprints = match path {
+        Some(path) => entries::read(path, "fingerprints")?,
+        None => entries::parse(DEFAULT_FINGERPRINTS, false, "fingerprints")?,
+    };
+    compile(fingerprints)
+}
@@ -1,54 +1,174 @@
-use std::collections::HashMap;
+use std::io::Read;
+use std::sync::Mutex;
 
+use hickory_proto::op::{Message, ResponseCode};
+use hickory_proto::rr::{RData, RecordType};
 use reqwest::header::USER_AGENT;
+use serde::Serialize;
 
 use crate::database::node::Type;
+use crate::findings::{Finding, Severity};
+use crate::modules::domain_takeover::delegation::Issue;
+use crate::modules::domain_takeover::fingerprints::{Provider, Status};
 use crate::modules::{Context, Module};
 use crate::session::Session;
-use crate::{events, flags, helpers, logger};
+use crate::{config, events, flags, helpers, logger};
+
+mod delegation;
+pub mod fingerprints;
+#[cfg(test)]
+mod tests;
+
+/// The maximum amount of CNAME records followed, in case they loop
+const MAX_CHAIN_LENGTH: usize = 16;
+
+/// The maximum amount of bytes read from a response when looking for a fingerprint
+const MAX_CONTENT_SIZE: u64 = 1024 * 1024;
+
+#[derive(Debug, Serialize)]
+pub struct Takeover {
+    pub provider: String,
+    pub status: Status,
+    /// The names the domain points at, in order
+    pub cname_chain: Vec<String>,
+    pub reason: String,
+}
 
 pub struct ModuleDomainTakeover {
-    platforms: HashMap<String, String>,
+    providers: Vec<Provider>,
+    processed_domains: Mutex<Vec<String>>,
 }
 
 impl ModuleDomainTakeover {
-    pub fn new() -> Self {
-        ModuleDomainTakeover {
-            platforms: HashMap::from([
-                (
-                    String::from("github"),
-                    String::from("<p><strong>There isn't a GitHub Pages site here.</strong></p>"),
-                ),
-                (
-                    String::from("glitch"),
-                    String::from("<h1>Well, you found a glitch.</h1>"),
-                ),
-                (
-                    String::from("heroku"),
-                    String::from(
-                        "<iframe src=\"//www.herokucdn.com/error-pages/no-such-app.html\"></iframe>",
-                    ),
-                ),
-                (
-                    String::from("netlify"),
-                    String::from("Not Found - Request ID: "),
-                ),
-                (
-                    String::from("railway"),
-                    String::from("Application not found"),
-                ),
-                (String::from("replit"), String::from("Not Found")),
-                (
-                    String::from("vercel"),
-                    String::from("The deployment could not be found on Vercel."),
-                ),
-            ]),
-        }
+    pub fn new(config: config::DomainTakeoverConfig) -> Result<Self, String> {
+        Ok(ModuleDomainTakeover {
+            // Platforms that aren't vulnerable are only listed to document them
+            providers: fingerprints::load(config.fingerprints.as_deref())?
+                .into_iter()
+                .filter(|provider| provider.status != Status::NotVulnerable)
+                .collect(),
+            processed_domains: Mutex::new(Vec::new()),
+        })
+    }
+
+    pub fn process(&self, domain: String) {
+        self.processed_domains.lock().unwrap().push(domain)
+    }
+
+    pub fn has_processed(&self, domain: String) -> bool {
+        self.processed_domains.lock().unwrap().contains(&domain)
     }
 
     fn name_with_platform(&self, platform: String) -> String {
         format!("{}({})", self.name(), platform)
     }
+
+    /// Checks whether the NS and MX records of the domain point at hosts someone else could claim
+    fn check_delegations(&self, session: &Session, domain: &str) -> Vec<Issue> {
+        let resolver = session.get_resolver();
+        let mut issues = Vec::new();
+        for nameserver in resolver.lookup(domain, RecordType::NS) {
+            if let Some(issue) = delegation::check_nameserver(resolver, domain, &nameserver) {
+                issues.push(issue);
+            }
+        }
+        for mx in resolver.lookup(domain, RecordType::MX) {
+            // A null MX record, `0 .`, has no target
+            let Some(mail_server) = mx.split_whitespace().nth(1) else {
+                continue;
+            };
+            if let Some(issue) = delegation::check_mail_server(resolver, mail_server) {
+                issues.push(issue);
+            }
+        }
+        issues
+    }
+
+    /// Fetches the status code and the content served for the domain, over HTTPS first and then over HTTP
+    fn fetch(&self, session: &Session, domain: &str) -> Option<(u16, String)> {
+        for scheme in ["https", "http"] {
+            let response = session
+                .get_http_client()
+                .get(format!("{}://{}", scheme, domain))
+                .header(USER_AGENT, helpers::ua::get_random())
+                .send();
+            if let Ok(response) = response {
+                let status = response.status().as_u16();
+                let mut content = Vec::new();
+                let _ = response.take(MAX_CONTENT_SIZE).read_to_end(&mut content);
+                return Some((status, String::from_utf8_lossy(&content).to_string()));
+            }
+        }
+        None
+    }
+}
+
+/// Returns the names the domain points at by following the CNAME records of the answer
+pub fn get_cname_chain(response: &Message, domain: &str) -> Vec<String> {
+    let mut chain = Vec::new();
+    let mut current = domain.trim_end_matches('.').to_lowercase();
+    while chain.len() < MAX_CHAIN_LENGTH {
+        let target = response
+            .answers()
+            .iter()
+            .find_map(|record| match record.data() {
+                Some(RData::CNAME(cname))
+                    if record
+                        .name()
+                        .to_ascii()
+                        .trim_end_matches('.')
+                        .eq_ignore_ascii_case(&current) =>
+                {
+                    Some(cname.to_ascii().trim_end_matches('.').to_lowercase())
+                }
+                _ => None,
+            });
+        match target {
+            Some(target) if !chain.contains(&target) => {
+                chain.push(target.clone());
+                current = target;
+            }
+            _ => break,
+        }
+    }
+    chain
+}
+
+/// Returns the platform the domain can be taken over on and the reason, given the platforms its CNAME chain points at
+///
+/// Only the platforms the chain points at are checked, so that a page merely containing a fingerprint isn't reported
+pub fn find_takeover<'a>(
+    providers: &[&'a Provider],
+    dangling: bool,
+    fetch: impl FnOnce() -> Option<(u16, String)>,
+) -> Option<(&'a Provider, &'static str)> {
+    if dangling {
+        return providers
+            .iter()
+            .find(|provider| provider.nxdomain)
+            .map(|provider| (*provider, "The CNAME target doesn't exist"));
+    }
+    if providers.is_empty() {
+        return None;
+    }
+    let (status, content) = fetch()?;
+    providers
+        .iter()
+        .find(|provider| provider.matches_response(status, &content))
+        .map(|provider| {
+            (
+                *provider,
+                "The platform serves the content of an unclaimed resource",
+            )
+        })
+}
+
+/// Formats the CNAME chain of a domain, e.g. `www.krypton.ninja -> krypton.github.io`
+pub fn format_chain(domain: &str, chain: &[String]) -> String {
+    std::iter::once(domain.to_string())
+        .chain(chain.iter().cloned())
+        .collect::<Vec<String>>()
+        .join(" -> ")
 }
 
 impl Module for ModuleDomainTakeover {
@@ -58,7 +178,7 @@ impl Module for ModuleDomainTakeover {
 
     fn description(&self) -> String {
         String::from(
-            "This module checks for the content of a domain to know whether it can be taken over",
+            "This module checks the CNAME records and the content of a domain to know whether it can be taken over",
         )
     }
 
@@ -74,36 +194,147 @@ impl Module for ModuleDomainTakeover {
             }
         };
 
-        let response = reqwest::blocking::Client::new()
-            .get(format!("https://{}", domain))
-            .header(USER_AGENT, helpers::ua::get_random())
-            .send();
-        match response {
-            Ok(response) => {
-                let body = response.text().unwrap_or_default();
-
-                for (platform, content) in self.platforms.iter() {
-                    if body.contains(content) {
-                        if let Some(parent) =
-                            session.get_database().search(Type::Domain, domain.clone())
-                        {
-                            parent.add_data(
-                                String::from("possible_takeover"),
-                                platform.to_string().into(),
-                            );
-                            parent.add_flag(flags::domain::POSSIBLE_TAKEOVER);
+        if self.has_processed(domain.to_string()) {
+            return Ok(());
+        }
+        self.process(domain.to_string());
+
+        let response = session
+            .get_resolver()
+            .query(&domain, RecordType::A)
+            .map_err(|e| format!("Failed resolving '{}': {}", domain, e))?;
+        let chain = get_cname_chain(&response, &domain);
+        // The response code is the one of the last name of the chain
+        let dangling = !chain.is_empty() && response.response_code() == ResponseCode::NXDomain;
+
+        let providers = self
+            .providers
+            .iter()
+            .filter(|provider| provider.matches_cname(&chain))
+            .collect::<Vec<&Provider>>();
+        let takeover = find_takeover(&providers, dangling, || self.fetch(session, &domain));
+
+        // Domains with CNAME records can't have NS or MX records of their own
+        let issues = if chain.is_empty() {
+            self.check_delegations(session, &domain)
+        } else {
+            Vec::new()
+        };
+
+        let mut database = session.get_database();
+        let Some(node) = database.search(Type::Domain, domain.clone()) else {
+            return Ok(());
+        };
+        match takeover {
+            Some((provider, reason)) => {
+                node.add_data(
+                    String::from("possible_takeover"),
+                    provider.name.clone().into(),
+                );
+                node.add_data(
+                    String::from("takeover"),
+                    serde_json::to_value(Takeover {
+                        provider: provider.name.clone(),
+                        status: provider.status,
+                        cname_chain: chain.clone(),
+                        reason: reason.to_string(),
+                    })
+                    .map_err(|e| e.to_string())?,
+                );
+                node.add_flag(flags::domain::POSSIBLE_TAKEOVER);
+                node.add_finding(Finding::new(
+                    self.name(),
+                    if provider.status == Status::EdgeCase {
+                        Severity::Medium
+                    } else {
+                        Severity::High
+                    },
+                    format!(
+                        "Possible takeover on {}{}",
+                        provider.name,
+                        if provider.status == Status::EdgeCase {
+                            " (edge case)"
+                        } else {
+                            ""
                         }
-                        logger::println(
-                            self.name_with_platform(platform.to_string()),
-                            format!("Domain takeover possible for '{}'", domain),
-                        );
-                        break;
-                    }
-                }
+                    ),
+                    Some(format!("{} ({})", format_chain(&domain, &chain), reason)),
+                ));
+                logger::println(
+                    self.name_with_platform(provider.name.clone()),
+                    format!("Domain takeover possible for '{}'", domain),
+                );
+            }
+            // Pointing at a name that doesn't exist is worth checking even when it can't be claimed on the platform
+            None if dangling => {
+                node.add_finding(Finding::new(
+                    self.name(),
+                    Severity::Medium,
+                    "Dangling CNAME record",
+                    Some(format!(
+                        "{}{}",
+                        format_chain(&domain, &chain),
+                        providers
+                            .first()
+                            .map(|provider| format!(" ({})", provider.name))
+                            .unwrap_or_default()
+                    )),
+                ));
+                logger::warn(
+                    self.name(),
+                    format!(
+                        "'{}' points at '{}', which doesn't exist",
+                        domain,
+                        chain.last().unwrap()
+                    ),
+                );
+            }
+            None => {}
+        }
 
-                Ok(())
+        if issues.is_empty() {
+            return Ok(());
+        }
+        if issues.iter().any(|issue| issue.takeover) {
+            node.add_flag(flags::domain::DELEGATION_TAKEOVER);
+        }
+        node.add_data(
+            String::from("delegation_takeover"),
+            serde_json::to_value(&issues).map_err(|e| e.to_string())?,
+        );
+        for issue in issues {
+            if issue.takeover {
+                logger::println(
+                    self.name(),
+                    format!(
+                        "Delegation takeover possible for '{}' through its {} record '{}'",
+                        domain, issue.record_type, issue.target
+                    ),
+                );
+            } else {
+                logger::warn(
+                    self.name(),
+                    format!(
+                        "The {} record '{}' of '{}' doesn't resolve",
+                        issue.record_type, issue.target, domain
+                    ),
+                );
             }
-            Err(_) => Err(format!("Failed performing a request to '{}'", domain)),
+            node.add_finding(Finding::new(
+                self.name(),
+                issue.severity,
+                if issue.takeover {
+                    format!("Possible {} delegation takeover", issue.record_type)
+                } else {
+                    format!(
+                        "{} record points at a host that doesn't resolve",
+                        issue.record_type
+                    )
+                },
+                Some(format!("{}: {}", issue.target, issue.reason)),
+            ));
         }
+
+        Ok(())
     }
 }
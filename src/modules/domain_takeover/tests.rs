@@ -0,0 +1,141 @@
+use std::str::FromStr;
+
+use hickory_proto::op::{Message, ResponseCode};
+use hickory_proto::rr::rdata::{A, CNAME};
+use hickory_proto::rr::{Name, RData, Record};
+
+use crate::helpers::entries;
+use crate::modules::domain_takeover::delegation;
+use crate::modules::domain_takeover::fingerprints::Provider;
+use crate::modules::domain_takeover::fingerprints::{self, Fingerprint, Status};
+use crate::modules::domain_takeover::{find_takeover, get_cname_chain};
+
+fn cname(name: &str, target: &str) -> Record {
+    Record::from_rdata(
+        Name::from_str(name).unwrap(),
+        300,
+        RData::CNAME(CNAME(Name::from_str(target).unwrap())),
+    )
+}
+
+#[test]
+fn follow_cname_chain() {
+    let mut response = Message::new();
+    response.add_answers([
+        cname("Www.Krypton.Ninja.", "krypton.azureedge.net."),
+        cname("krypton.azureedge.net.", "krypton.azurewebsites.net."),
+        cname("krypton.azurewebsites.net.", "krypton.azureedge.net."),
+        Record::from_rdata(
+            Name::from_str("krypton.azurewebsites.net.").unwrap(),
+            300,
+            RData::A(A::new(127, 0, 0, 1)),
+        ),
+    ]);
+    let chain = get_cname_chain(&response, "www.krypton.ninja");
+    assert_eq!(
+        chain,
+        vec!["krypton.azureedge.net", "krypton.azurewebsites.net"]
+    );
+    assert!(get_cname_chain(&response, "go.krypton.ninja").is_empty());
+}
+
+#[test]
+fn match_provider() {
+    let providers = fingerprints::load(None).unwrap();
+    let chain = vec![String::from("krypton.github.io")];
+    let matched = providers
+        .iter()
+        .filter(|provider| provider.matches_cname(&chain))
+        .collect::<Vec<_>>();
+    assert_eq!(matched.len(), 1);
+    assert_eq!(matched[0].name, "github");
+    let content = "<p><strong>There isn't a GitHub Pages site here.</strong></p>";
+    assert!(matched[0].matches_response(404, content));
+    assert!(!matched[0].matches_response(200, content));
+    assert!(!matched[0].matches_response(404, "<html></html>"));
+}
+
+#[test]
+fn require_cname_match() {
+    let providers = fingerprints::load(None).unwrap();
+    let content = "<p><strong>There isn't a GitHub Pages site here.</strong></p>";
+    let fetch = || Some((404, content.to_string()));
+
+    // Domains without a CNAME record don't point at any platform, whatever their content is
+    let matched = providers
+        .iter()
+        .filter(|provider| provider.matches_cname(&[]))
+        .collect::<Vec<&Provider>>();
+    assert!(matched.is_empty());
+    assert!(find_takeover(&matched, false, fetch).is_none());
+
+    let chain = vec![String::from("krypton.github.io")];
+    let matched = providers
+        .iter()
+        .filter(|provider| provider.matches_cname(&chain))
+        .collect::<Vec<&Provider>>();
+    assert_eq!(
+        find_takeover(&matched, false, fetch).unwrap().0.name,
+        "github"
+    );
+    // A GitHub Pages name that doesn't exist can't be claimed, it is only a dangling record
+    assert!(find_takeover(&matched, true, fetch).is_none());
+
+    let chain = vec![String::from("krypton.azurewebsites.net")];
+    let matched = providers
+        .iter()
+        .filter(|provider| provider.matches_cname(&chain))
+        .collect::<Vec<&Provider>>();
+    assert_eq!(
+        find_takeover(&matched, true, || None).unwrap().0.name,
+        "azure"
+    );
+}
+
+#[test]
+fn skip_generic_not_found_pages() {
+    let providers = fingerprints::load(None).unwrap();
+    let get = |name: &str| {
+        providers
+            .iter()
+            .find(|provider| provider.name == name)
+            .unwrap()
+    };
+    let content = "<html><h1>Not Found</h1><p>Application not found</p></html>";
+    assert!(!get("replit").matches_response(404, content));
+    assert!(!get("railway").matches_response(404, content));
+    assert!(get("replit").matches_response(404, "<title>Replit</title><h1>Not Found</h1>"));
+    assert!(get("railway").matches_response(
+        404,
+        "<h1>Not Found</h1><p>The train has not arrived at the station.</p>"
+    ));
+}
+
+#[test]
+fn validate_fingerprints() {
+    let fingerprints = entries::parse::<Fingerprint>(
+        r#"[
+            {"service": "krypton", "status": "Edge case", "cname": ["krypton.ninja"], "regex": ["No such (site|app)"]},
+            {"service": "krypton", "cname": ["krypton.ninja"], "regex": ["("]},
+            {"service": "nothing", "nxdomain": true}
+        ]"#,
+        true,
+        "fingerprints",
+    )
+    .unwrap();
+    assert_eq!(fingerprints[0].status, Status::EdgeCase);
+    assert_eq!(fingerprints::validate(&fingerprints).len(), 3);
+    assert!(
+        fingerprints::compile(fingerprints[..1].to_vec()).unwrap()[0]
+            .matches_response(404, "No such app")
+    );
+}
+
+#[test]
+fn detect_lame_delegation() {
+    let mut response = Message::new();
+    response.set_response_code(ResponseCode::Refused);
+    assert!(delegation::is_lame(&response));
+    response.set_response_code(ResponseCode::NoError);
+    assert!(!delegation::is_lame(&response));
+}
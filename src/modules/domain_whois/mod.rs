@@ -0,0 +1,440 @@
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
+use hickory_proto::rr::RecordType;
+use reqwest::StatusCode;
+use reqwest::header::{ACCEPT, USER_AGENT};
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::database::node::Type;
+use crate::findings::{Finding, Severity};
+use crate::helpers::dns::get_registrable_domain;
+use crate::modules::{Context, Module};
+use crate::session::Session;
+use crate::{config, events, flags, helpers, logger};
+
+mod rdap;
+#[cfg(test)]
+mod tests;
+mod whois;
+
+/// The IANA file listing the RDAP servers of each TLD
+const RDAP_BOOTSTRAP_URL: &str = "https://data.iana.org/rdap/dns.json";
+
+/// The WHOIS server referring to the one of each TLD
+const IANA_WHOIS_SERVER: &str = "whois.iana.org";
+
+/// The formats of the dates found in WHOIS responses, besides RFC 3339
+const DATE_TIME_FORMATS: [&str; 3] = [
+    "%Y-%m-%d %H:%M:%S",
+    "%Y-%m-%dT%H:%M:%S",
+    "%Y.%m.%d %H:%M:%S",
+];
+const DATE_FORMATS: [&str; 6] = [
+    "%Y-%m-%d", "%d-%b-%Y", "%Y.%m.%d", "%d.%m.%Y", "%Y/%m/%d", "%d/%m/%Y",
+];
+
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct Registration {
+    pub domain: String,
+    /// Either `rdap` or `whois`
+    pub source: String,
+    pub registrar: Option<String>,
+    /// The organization of the registrant, when not redacted
+    pub registrant: Option<String>,
+    /// The creation date, in RFC 3339
+    pub created: Option<String>,
+    /// The expiry date, in RFC 3339
+    pub expires: Option<String>,
+    pub nameservers: Vec<String>,
+    pub status: Vec<String>,
+}
+
+impl Registration {
+    /// Returns the amount of days left before the domain expires, which is negative once it has expired
+    pub fn get_days_left(&self, now: DateTime<Utc>) -> Option<i64> {
+        let expires = DateTime::parse_from_rfc3339(self.expires.as_deref()?).ok()?;
+        Some((expires.with_timezone(&Utc) - now).num_days())
+    }
+}
+
+/// A domain another one depends on through its NS or MX records
+#[derive(Clone, Debug, Serialize)]
+pub struct Dependency {
+    pub record_type: String,
+    #[serde(flatten)]
+    pub registration: Registration,
+}
+
+/// Parses a date found in a registration and formats it in RFC 3339
+pub fn format_date(date: &str) -> Option<String> {
+    let date = date.trim();
+    if let Ok(date) = DateTime::parse_from_rfc3339(date) {
+        return Some(date.with_timezone(&Utc).to_rfc3339());
+    }
+    // Some registries append the timezone after the time
+    let date = date
+        .split_whitespace()
+        .take(2)
+        .collect::<Vec<&str>>()
+        .join(" ");
+    let date = date.trim_end_matches('Z');
+    DATE_TIME_FORMATS
+        .iter()
+        .find_map(|format| NaiveDateTime::parse_from_str(date, format).ok())
+        .or_else(|| {
+            let date = date.split_whitespace().next().unwrap_or_default();
+            DATE_FORMATS
+                .iter()
+                .find_map(|format| NaiveDate::parse_from_str(date, format).ok())
+                .and_then(|date| date.and_hms_opt(0, 0, 0))
+        })
+        .map(|date| date.and_utc().to_rfc3339())
+}
+
+pub struct ModuleDomainWhois {
+    config: config::DomainWhoisConfig,
+    bootstrap: OnceLock<Option<Value>>,
+    whois_servers: Mutex<HashMap<String, Option<String>>>,
+    registrations: Mutex<HashMap<String, Option<Registration>>>,
+    processed_domains: Mutex<Vec<String>>,
+}
+
+impl ModuleDomainWhois {
+    pub fn new(config: config::DomainWhoisConfig) -> Self {
+        ModuleDomainWhois {
+            config,
+            bootstrap: OnceLock::new(),
+            whois_servers: Mutex::new(HashMap::new()),
+            registrations: Mutex::new(HashMap::new()),
+            processed_domains: Mutex::new(Vec::new()),
+        }
+    }
+
+    pub fn process(&self, domain: String) {
+        self.processed_domains.lock().unwrap().push(domain)
+    }
+
+    pub fn has_processed(&self, domain: String) -> bool {
+        self.processed_domains.lock().unwrap().contains(&domain)
+    }
+
+    fn get_timeout(&self) -> Duration {
+        Duration::from_millis(self.config.timeout.unwrap_or(10000))
+    }
+
+    fn get_rdap_servers(&self, session: &Session, tld: &str) -> Vec<String> {
+        if let Some(server) = &self.config.rdap_server {
+            return vec![server.clone()];
+        }
+        let bootstrap = self.bootstrap.get_or_init(|| {
+            session
+                .get_http_client()
+                .get(RDAP_BOOTSTRAP_URL)
+                .header(USER_AGENT, helpers::ua::get_random())
+                .timeout(self.get_timeout())
+                .send()
+                .and_then(|response| response.json::<Value>())
+                .ok()
+        });
+        bootstrap
+            .as_ref()
+            .map(|bootstrap| rdap::get_servers(bootstrap, tld))
+            .unwrap_or_default()
+    }
+
+    /// Queries RDAP, which is `None` when the domain isn't registered
+    fn query_rdap(&self, session: &Session, domain: &str) -> Result<Option<Registration>, String> {
+        let tld = domain.rsplit('.').next().unwrap_or_default();
+        let mut error = format!("No RDAP server known for '.{}'", tld);
+        for server in self.get_rdap_servers(session, tld) {
+            let response = session
+                .get_http_client()
+                .get(format!(
+                    "{}/domain/{}",
+                    server.trim_end_matches('/'),
+                    domain
+                ))
+                .header(ACCEPT, "application/rdap+json")
+                .header(USER_AGENT, helpers::ua::get_random())
+                .timeout(self.get_timeout())
+                .send();
+            match response {
+                Ok(response) if response.status() == StatusCode::NOT_FOUND => return Ok(None),
+                Ok(response) if response.status().is_success() => {
+                    let response = response.json::<Value>().map_err(|e| e.to_string())?;
+                    return Ok(rdap::parse(domain, &response));
+                }
+                Ok(response) => {
+                    error = format!("'{}' responded with {}", server, response.status())
+                }
+                Err(e) => error = format!("Failed querying '{}': {}", server, e),
+            }
+        }
+        Err(error)
+    }
+
+    fn get_whois_server(&self, session: &Session, tld: &str) -> Option<String> {
+        if let Some(server) = &self.config.whois_server {
+            return Some(server.clone());
+        }
+        if let Some(server) = self.whois_servers.lock().unwrap().get(tld) {
+            return server.clone();
+        }
+        let server = whois::query(
+            session.get_resolver(),
+            IANA_WHOIS_SERVER,
+            tld,
+            self.get_timeout(),
+        )
+        .ok()
+        .and_then(|response| whois::get_referral(&response));
+        self.whois_servers
+            .lock()
+            .unwrap()
+            .insert(tld.to_string(), server.clone());
+        server
+    }
+
+    fn query_whois(&self, session: &Session, domain: &str) -> Result<Option<Registration>, String> {
+        let tld = domain.rsplit('.').next().unwrap_or_default();
+        let server = self
+            .get_whois_server(session, tld)
+            .ok_or(format!("No WHOIS server known for '.{}'", tld))?;
+        let response = whois::query(session.get_resolver(), &server, domain, self.get_timeout())?;
+        Ok(whois::parse(domain, &response))
+    }
+
+    /// Returns the registration of a domain from RDAP, or from WHOIS if RDAP isn't available
+    fn lookup(&self, session: &Session, domain: &str) -> Option<Registration> {
+        if let Some(registration) = self.registrations.lock().unwrap().get(domain) {
+            return registration.clone();
+        }
+        let registration = self.query_rdap(session, domain).or_else(|e| {
+            if session.get_state().is_debug_or_verbose() {
+                logger::info(
+                    self.name(),
+                    format!("Falling back to WHOIS for '{}': {}", domain, e),
+                );
+            }
+            self.query_whois(session, domain)
+        });
+        let registration = match registration {
+            Ok(registration) => registration,
+            Err(e) => {
+                logger::error(
+                    self.name(),
+                    format!("Failed retrieving the registration of '{}': {}", domain, e),
+                );
+                None
+            }
+        };
+        self.registrations
+            .lock()
+            .unwrap()
+            .insert(domain.to_string(), registration.clone());
+        registration
+    }
+
+    /// Returns the registrations of the domains the NS and MX records of the domain point at
+    fn get_dependencies(&self, session: &Session, domain: &str) -> Vec<Dependency> {
+        let resolver = session.get_resolver();
+        let apex = get_registrable_domain(domain);
+        let mut dependencies: Vec<Dependency> = Vec::new();
+        for (record_type, targets) in [
+            ("NS", resolver.lookup(domain, RecordType::NS)),
+            (
+                "MX",
+                resolver
+                    .lookup(domain, RecordType::MX)
+                    .iter()
+                    .filter_map(|mx| mx.split_whitespace().nth(1).map(|host| host.to_string()))
+                    .collect(),
+            ),
+        ] {
+            for target in targets {
+                let dependency = get_registrable_domain(&target);
+                if dependency == apex
+                    || dependencies.iter().any(|known| {
+                        known.record_type == record_type && known.registration.domain == dependency
+                    })
+                {
+                    continue;
+                }
+                if let Some(registration) = self.lookup(session, &dependency) {
+                    dependencies.push(Dependency {
+                        record_type: record_type.to_string(),
+                        registration,
+                    });
+                }
+            }
+        }
+        dependencies
+    }
+}
+
+/// Turns the registrations expiring within the window, in days, into findings
+pub fn evaluate(
+    module: &str,
+    registration: Option<&Registration>,
+    dependencies: &[Dependency],
+    window: i64,
+    now: DateTime<Utc>,
+) -> Vec<Finding> {
+    let mut findings = Vec::new();
+    if let Some(registration) = registration
+        && let Some(days_left) = registration.get_days_left(now)
+        && days_left <= window
+    {
+        findings.push(if days_left < 0 {
+            Finding::new(
+                module,
+                Severity::High,
+                "Domain registration has expired",
+                registration.expires.clone(),
+            )
+        } else {
+            Finding::new(
+                module,
+                Severity::Medium,
+                format!("Domain registration expires in {} days", days_left),
+                registration.expires.clone(),
+            )
+        });
+    }
+    for dependency in dependencies {
+        let registration = &dependency.registration;
+        if let Some(days_left) = registration.get_days_left(now)
+            && days_left <= window
+        {
+            findings.push(Finding::new(
+                module,
+                if days_left < 0 {
+                    Severity::High
+                } else {
+                    Severity::Medium
+                },
+                format!(
+                    "Domain of the {} records {}",
+                    dependency.record_type,
+                    if days_left < 0 {
+                        String::from("has expired")
+                    } else {
+                        format!("expires in {} days", days_left)
+                    }
+                ),
+                Some(format!(
+                    "{}: {}",
+                    registration.domain,
+                    registration.expires.as_deref().unwrap_or_default()
+                )),
+            ));
+        }
+    }
+    findings
+}
+
+impl Module for ModuleDomainWhois {
+    fn name(&self) -> String {
+        String::from("domain:whois")
+    }
+
+    fn description(&self) -> String {
+        String::from(
+            "This module retrieves the registration of a domain and of the domains it depends on, to know whether they're about to expire",
+        )
+    }
+
+    fn subscribers(&self) -> Vec<events::Type> {
+        vec![events::Type::DiscoveredDomain(String::new())]
+    }
+
+    fn execute(&self, session: &Session, context: Context) -> Result<(), String> {
+        let domain = match context {
+            Context::Domain(domain) => domain,
+            _ => {
+                return Err("Received wrong context, exiting module".to_string());
+            }
+        };
+
+        if self.has_processed(domain.to_string()) {
+            return Ok(());
+        }
+        self.process(domain.to_string());
+
+        // Only apex domains are registered, the target domain standing for its own one
+        let apex = get_registrable_domain(&domain);
+        let registration = if domain == apex || domain == session.get_args().domain {
+            self.lookup(session, &apex)
+        } else {
+            None
+        };
+        let dependencies = self.get_dependencies(session, &domain);
+        if registration.is_none() && dependencies.is_empty() {
+            return Ok(());
+        }
+
+        let findings = evaluate(
+            &self.name(),
+            registration.as_ref(),
+            &dependencies,
+            self.config.expiry_window.unwrap_or(30),
+            Utc::now(),
+        );
+        if let Some(registration) = &registration {
+            logger::println(
+                self.name(),
+                format!(
+                    "Retrieved the registration of '{}' from {}{}",
+                    registration.domain,
+                    registration.source.to_uppercase(),
+                    match &registration.expires {
+                        Some(expires) => format!(", expiring on {}", expires),
+                        None => String::new(),
+                    }
+                ),
+            );
+        }
+        for finding in findings.iter() {
+            logger::warn(
+                self.name(),
+                format!(
+                    "{} for '{}'{}",
+                    finding.title,
+                    domain,
+                    finding
+                        .evidence
+                        .as_ref()
+                        .map(|evidence| format!(" ({})", evidence))
+                        .unwrap_or_default()
+                ),
+            );
+        }
+
+        if let Some(node) = session.get_database().search(Type::Domain, domain.clone()) {
+            if !findings.is_empty() {
+                node.add_flag(flags::domain::EXPIRING);
+            }
+            if let Some(registration) = registration {
+                node.add_data(
+                    String::from("whois"),
+                    serde_json::to_value(registration).map_err(|e| e.to_string())?,
+                );
+            }
+            if !dependencies.is_empty() {
+                node.add_data(
+                    String::from("whois_dependencies"),
+                    serde_json::to_value(dependencies).map_err(|e| e.to_string())?,
+                );
+            }
+            for finding in findings {
+                node.add_finding(finding);
+            }
+        }
+
+        Ok(())
+    }
+}
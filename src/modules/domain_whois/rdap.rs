@@ -0,0 +1,88 @@
+use serde_json::Value;
+
+use crate::modules::domain_whois::{Registration, format_date};
+
+/// Returns the RDAP base URLs serving the TLD, from the IANA bootstrap file
+pub fn get_servers(bootstrap: &Value, tld: &str) -> Vec<String> {
+    bootstrap["services"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .find(|service| {
+            service[0].as_array().into_iter().flatten().any(|entry| {
+                entry
+                    .as_str()
+                    .is_some_and(|entry| entry.eq_ignore_ascii_case(tld))
+            })
+        })
+        .and_then(|service| service[1].as_array())
+        .map(|urls| {
+            urls.iter()
+                .filter_map(|url| url.as_str())
+                .map(|url| url.to_string())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Returns a property of the vCard of an entity, e.g. `fn` or `org`
+fn get_vcard_property(entity: &Value, name: &str) -> Option<String> {
+    entity["vcardArray"][1]
+        .as_array()?
+        .iter()
+        .find(|property| property[0].as_str() == Some(name))
+        .and_then(|property| property[3].as_str())
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+fn get_entity<'a>(response: &'a Value, role: &str) -> Option<&'a Value> {
+    response["entities"].as_array()?.iter().find(|entity| {
+        entity["roles"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .any(|entry| entry.as_str() == Some(role))
+    })
+}
+
+fn get_event(response: &Value, action: &str) -> Option<String> {
+    response["events"]
+        .as_array()?
+        .iter()
+        .find(|event| event["eventAction"].as_str() == Some(action))
+        .and_then(|event| event["eventDate"].as_str())
+        .and_then(format_date)
+}
+
+/// Parses an RDAP domain response
+pub fn parse(domain: &str, response: &Value) -> Option<Registration> {
+    if response["objectClassName"].as_str() != Some("domain") {
+        return None;
+    }
+    Some(Registration {
+        domain: domain.to_string(),
+        source: String::from("rdap"),
+        registrar: get_entity(response, "registrar")
+            .and_then(|entity| get_vcard_property(entity, "fn")),
+        registrant: get_entity(response, "registrant").and_then(|entity| {
+            get_vcard_property(entity, "org").or_else(|| get_vcard_property(entity, "fn"))
+        }),
+        created: get_event(response, "registration"),
+        expires: get_event(response, "expiration"),
+        nameservers: response["nameservers"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|nameserver| nameserver["ldhName"].as_str())
+            .map(|nameserver| nameserver.trim_end_matches('.').to_lowercase())
+            .collect(),
+        status: response["status"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|status| status.as_str())
+            .map(|status| status.to_string())
+            .collect(),
+    })
+}
@@ -0,0 +1,128 @@
+use chrono::{TimeZone, Utc};
+use serde_json::json;
+
+use crate::modules::domain_whois::{Dependency, Registration, evaluate, format_date, rdap, whois};
+
+#[test]
+fn format_registration_dates() {
+    let expected = Some(String::from("2025-08-13T04:00:00+00:00"));
+    assert_eq!(format_date("2025-08-13T04:00:00Z"), expected);
+    assert_eq!(format_date("2025-08-13 04:00:00 CLST"), expected);
+    assert_eq!(
+        format_date("13-aug-2025"),
+        Some(String::from("2025-08-13T00:00:00+00:00"))
+    );
+    assert_eq!(format_date("soon"), None);
+}
+
+#[test]
+fn parse_whois_response() {
+    let response = "% Registry data\n\
+        Domain Name: KRYPTON.NINJA\n\
+        Registrar: Krypton Registrar, LLC\n\
+        Creation Date: 2019-01-02T03:04:05Z\n\
+        Registry Expiry Date: 2025-08-13T04:00:00Z\n\
+        Domain Status: clientTransferProhibited https://icann.org/epp#clientTransferProhibited\n\
+        Name Server: NS1.KRYPTON.NINJA\n\
+        Name Server: ns2.krypton.ninja 192.0.2.1\n";
+    let registration = whois::parse("krypton.ninja", response).unwrap();
+    assert_eq!(
+        registration.registrar.as_deref(),
+        Some("Krypton Registrar, LLC")
+    );
+    assert_eq!(
+        registration.expires.as_deref(),
+        Some("2025-08-13T04:00:00+00:00")
+    );
+    assert_eq!(
+        registration.nameservers,
+        vec!["ns1.krypton.ninja", "ns2.krypton.ninja"]
+    );
+    assert_eq!(registration.status, vec!["clientTransferProhibited"]);
+    assert!(whois::parse("krypton.ninja", "No match for \"KRYPTON.NINJA\".").is_none());
+    assert_eq!(
+        whois::get_referral("refer:        whois.nic.ninja\n").as_deref(),
+        Some("whois.nic.ninja")
+    );
+}
+
+#[test]
+fn parse_rdap_response() {
+    let response = json!({
+        "objectClassName": "domain",
+        "ldhName": "krypton.ninja",
+        "status": ["active"],
+        "events": [
+            {"eventAction": "registration", "eventDate": "2019-01-02T03:04:05Z"},
+            {"eventAction": "expiration", "eventDate": "2025-08-13T04:00:00Z"}
+        ],
+        "entities": [{
+            "roles": ["registrar"],
+            "vcardArray": ["vcard", [["version", {}, "text", "4.0"], ["fn", {}, "text", "Krypton Registrar"]]]
+        }],
+        "nameservers": [{"ldhName": "NS1.KRYPTON.NINJA"}]
+    });
+    let registration = rdap::parse("krypton.ninja", &response).unwrap();
+    assert_eq!(registration.registrar.as_deref(), Some("Krypton Registrar"));
+    assert_eq!(registration.registrant, None);
+    assert_eq!(
+        registration.created.as_deref(),
+        Some("2019-01-02T03:04:05+00:00")
+    );
+    assert_eq!(registration.nameservers, vec!["ns1.krypton.ninja"]);
+
+    let bootstrap = json!({"services": [[["com", "ninja"], ["https://rdap.krypton.ninja/"]]]});
+    assert_eq!(
+        rdap::get_servers(&bootstrap, "ninja"),
+        vec!["https://rdap.krypton.ninja/"]
+    );
+    assert!(rdap::get_servers(&bootstrap, "org").is_empty());
+}
+
+#[test]
+fn evaluate_expiring_registrations() {
+    let now = Utc.with_ymd_and_hms(2025, 8, 1, 0, 0, 0).unwrap();
+    let registration = Registration {
+        domain: String::from("krypton.ninja"),
+        expires: Some(String::from("2025-08-13T04:00:00+00:00")),
+        ..Default::default()
+    };
+    let dependency = Dependency {
+        record_type: String::from("NS"),
+        registration: Registration {
+            domain: String::from("dns.ninja"),
+            expires: Some(String::from("2025-07-01T00:00:00+00:00")),
+            ..Default::default()
+        },
+    };
+    let findings = evaluate("domain:whois", Some(&registration), &[dependency], 30, now);
+    assert_eq!(findings.len(), 2);
+    assert_eq!(findings[0].title, "Domain registration expires in 12 days");
+    assert_eq!(findings[1].title, "Domain of the NS records has expired");
+    assert!(evaluate("domain:whois", Some(&registration), &[], 7, now).is_empty());
+}
+
+#[test]
+fn parse_whois_servers() {
+    assert_eq!(
+        whois::parse_server("whois.nic.ninja"),
+        Some(("whois.nic.ninja", 43))
+    );
+    assert_eq!(
+        whois::parse_server("whois.nic.ninja:4343"),
+        Some(("whois.nic.ninja", 4343))
+    );
+    assert_eq!(
+        whois::parse_server("127.0.0.1:4343"),
+        Some(("127.0.0.1", 4343))
+    );
+    assert_eq!(
+        whois::parse_server("2001:db8::1"),
+        Some(("2001:db8::1", 43))
+    );
+    assert_eq!(
+        whois::parse_server("[2001:db8::1]:4343"),
+        Some(("2001:db8::1", 4343))
+    );
+    assert_eq!(whois::parse_server("whois.nic.ninja:port"), None);
+}
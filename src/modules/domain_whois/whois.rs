@@ -0,0 +1,156 @@
+use std::io::{Read, Write};
+use std::net::{IpAddr, SocketAddr, TcpStream};
+use std::time::Duration;
+
+use crate::helpers::dns::Resolver;
+use crate::modules::domain_whois::{Registration, format_date};
+
+/// The maximum amount of bytes read from a WHOIS response
+const MAX_RESPONSE_SIZE: u64 = 1024 * 1024;
+
+const REGISTRAR_KEYS: [&str; 3] = ["registrar", "registrar name", "sponsoring registrar"];
+const REGISTRANT_KEYS: [&str; 4] = [
+    "registrant organization",
+    "registrant organisation",
+    "registrant",
+    "org",
+];
+const CREATED_KEYS: [&str; 6] = [
+    "creation date",
+    "created",
+    "created on",
+    "registered on",
+    "registration time",
+    "domain registration date",
+];
+const EXPIRES_KEYS: [&str; 8] = [
+    "registry expiry date",
+    "registrar registration expiration date",
+    "expiration date",
+    "expiry date",
+    "expire date",
+    "expires",
+    "expires on",
+    "paid-till",
+];
+const NAMESERVER_KEYS: [&str; 3] = ["name server", "nserver", "nameserver"];
+const STATUS_KEYS: [&str; 2] = ["domain status", "status"];
+
+/// Splits a WHOIS server given as `host` or `host:port` into its host and port
+pub fn parse_server(server: &str) -> Option<(&str, u16)> {
+    if let Ok(socket_addr) = server.parse::<SocketAddr>() {
+        let host = server.strip_suffix(&format!(":{}", socket_addr.port()))?;
+        return Some((
+            host.trim_start_matches('[').trim_end_matches(']'),
+            socket_addr.port(),
+        ));
+    }
+    match server.rsplit_once(':') {
+        // IPv6 addresses contain colons without having a port
+        Some((host, port)) if !host.contains(':') => Some((host, port.parse().ok()?)),
+        _ => Some((server, 43)),
+    }
+}
+
+/// Sends a query to a WHOIS server, given as `host` or `host:port`
+pub fn query(
+    resolver: &Resolver,
+    server: &str,
+    query: &str,
+    timeout: Duration,
+) -> Result<String, String> {
+    let (host, port) = parse_server(server).ok_or(format!("Invalid WHOIS server '{}'", server))?;
+    let ip_addr = match host.parse::<IpAddr>() {
+        Ok(ip_addr) => ip_addr,
+        Err(_) => *resolver
+            .lookup_ip(host)
+            .first()
+            .ok_or(format!("Failed resolving '{}'", server))?,
+    };
+    let socket_addr = SocketAddr::new(ip_addr, port);
+    let mut stream = TcpStream::connect_timeout(&socket_addr, timeout)
+        .map_err(|e| format!("Failed connecting to '{}': {}", server, e))?;
+    let _ = stream.set_read_timeout(Some(timeout));
+    let _ = stream.set_write_timeout(Some(timeout));
+    stream
+        .write_all(format!("{}\r\n", query).as_bytes())
+        .map_err(|e| e.to_string())?;
+    let mut response = Vec::new();
+    stream
+        .take(MAX_RESPONSE_SIZE)
+        .read_to_end(&mut response)
+        .map_err(|e| format!("Failed reading the response of '{}': {}", server, e))?;
+    Ok(String::from_utf8_lossy(&response).to_string())
+}
+
+/// Returns the `key: value` lines of a response, with lowercased keys
+fn get_fields(response: &str) -> Vec<(String, String)> {
+    response
+        .lines()
+        .filter(|line| !line.trim_start().starts_with(['%', '#', '>']))
+        .filter_map(|line| {
+            let (key, value) = line.split_once(':')?;
+            let value = value.trim();
+            (!value.is_empty()).then(|| (key.trim().to_lowercase(), value.to_string()))
+        })
+        .collect()
+}
+
+fn get_field(fields: &[(String, String)], keys: &[&str]) -> Option<String> {
+    keys.iter().find_map(|key| {
+        fields
+            .iter()
+            .find(|(field, _)| field == key)
+            .map(|(_, value)| value.clone())
+    })
+}
+
+fn get_all_fields(fields: &[(String, String)], keys: &[&str]) -> Vec<String> {
+    fields
+        .iter()
+        .filter(|(field, _)| keys.contains(&field.as_str()))
+        .map(|(_, value)| value.clone())
+        .collect()
+}
+
+/// Returns the WHOIS server an IANA response refers to for a TLD
+pub fn get_referral(response: &str) -> Option<String> {
+    get_field(&get_fields(response), &["refer", "whois"])
+}
+
+/// Parses a WHOIS response, whose format depends on the registry
+pub fn parse(domain: &str, response: &str) -> Option<Registration> {
+    let fields = get_fields(response);
+    let mut nameservers = Vec::new();
+    for nameserver in get_all_fields(&fields, &NAMESERVER_KEYS) {
+        // Some registries append the addresses of the nameservers
+        let nameserver = nameserver
+            .split_whitespace()
+            .next()
+            .unwrap_or_default()
+            .trim_end_matches('.')
+            .to_lowercase();
+        if !nameserver.is_empty() && !nameservers.contains(&nameserver) {
+            nameservers.push(nameserver);
+        }
+    }
+    let registration = Registration {
+        domain: domain.to_string(),
+        source: String::from("whois"),
+        registrar: get_field(&fields, &REGISTRAR_KEYS),
+        registrant: get_field(&fields, &REGISTRANT_KEYS),
+        created: get_field(&fields, &CREATED_KEYS).and_then(|date| format_date(&date)),
+        expires: get_field(&fields, &EXPIRES_KEYS).and_then(|date| format_date(&date)),
+        nameservers,
+        // Statuses are usually followed by a link to their description
+        status: get_all_fields(&fields, &STATUS_KEYS)
+            .iter()
+            .filter_map(|status| status.split_whitespace().next())
+            .map(|status| status.to_string())
+            .collect(),
+    };
+    (registration.registrar.is_some()
+        || registration.expires.is_some()
+        || !registration.nameservers.is_empty())
+    .then_some(registration)
+}
@@ -4,12 +4,10 @@ use std::fmt;
 use regex::Regex;
 use reqwest::header::USER_AGENT;
 use serde::{Deserialize, Serialize};
-use serde_json::Value;
 
-use crate::database::node::{Node, Type};
 use crate::modules::{Context, Module};
 use crate::session::Session;
-use crate::{config, events, helpers, logger};
+use crate::{config, events, logger};
 
 #[derive(
     Copy, Clone, Debug, Default, Deserialize, PartialEq, Eq, PartialOrd, Ord, Serialize, Hash,
@@ -163,33 +161,11 @@ impl Module for ModuleDork {
         match self.get_domains(session, domain.clone(), search_engine) {
             Ok(domains) => {
                 for subdomain in domains {
-                    if !session
-                        .get_state()
-                        .has_discovered_domain(subdomain.to_string())
-                    {
-                        logger::println(
-                            self.name_with_search_engine(search_engine),
-                            format!("Discovered '{}' as a new subdomain", subdomain),
-                        );
-
-                        if let Some(parent) =
-                            session.get_database().search(Type::Domain, domain.clone())
-                        {
-                            let mut new_node = Node::new(Type::Domain, subdomain.to_string());
-                            if let Some(ip_addr) = helpers::network::get_ip_addr(&subdomain) {
-                                new_node.add_data(
-                                    String::from("ip"),
-                                    Value::String(ip_addr.to_string()),
-                                );
-                                if let Some(geoinfo) = helpers::network::geolocate_ip(ip_addr) {
-                                    new_node.add_data(String::from("geoinfo"), geoinfo.into())
-                                }
-                            }
-                            parent.connect(new_node);
-                        }
-                        session.get_state().discover_domain(subdomain.to_string());
-                        session.emit(events::Type::DiscoveredDomain(subdomain.to_string()));
-                    }
+                    session.discover_domain(
+                        &self.name_with_search_engine(search_engine),
+                        subdomain,
+                        Some(&domain),
+                    );
                 }
             }
             Err(e) => logger::error(self.name_with_search_engine(search_engine), e),
@@ -198,19 +174,11 @@ impl Module for ModuleDork {
         match self.get_emails(session, domain.clone(), search_engine) {
             Ok(emails) => {
                 for email in emails {
-                    if !session.get_state().has_discovered_email(email.to_string()) {
-                        logger::println(
-                            self.name_with_search_engine(search_engine),
-                            format!("Discovered '{}' as a new email", email),
-                        );
-
-                        if let Some(parent) =
-                            session.get_database().search(Type::Domain, domain.clone())
-                        {
-                            parent.connect(Node::new(Type::Email, email.to_string()));
-                        }
-                        session.get_state().discover_email(email.to_string());
-                    }
+                    session.discover_email(
+                        &self.name_with_search_engine(search_engine),
+                        email,
+                        &domain,
+                    );
                 }
             }
             Err(e) => logger::error(self.name_with_search_engine(search_engine), e),
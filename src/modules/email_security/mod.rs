@@ -0,0 +1,345 @@
+use std::io::Read;
+use std::sync::Mutex;
+
+use hickory_proto::rr::RecordType;
+use reqwest::header::USER_AGENT;
+use serde::Serialize;
+
+use crate::database::node::Type;
+use crate::findings::{Finding, Severity};
+use crate::modules::email_security::records::{Bimi, Dkim, Dmarc, MtaSts, TlsRpt};
+use crate::modules::email_security::spf::Spf;
+use crate::modules::{Context, Module};
+use crate::session::Session;
+use crate::{config, events, flags, helpers, logger};
+
+mod records;
+mod spf;
+#[cfg(test)]
+mod tests;
+
+/// The DKIM selectors probed when none are configured, used by common email providers
+const DEFAULT_DKIM_SELECTORS: [&str; 20] = [
+    "default",
+    "dkim",
+    "google",
+    "k1",
+    "k2",
+    "k3",
+    "mail",
+    "mandrill",
+    "mxvault",
+    "pm",
+    "protonmail",
+    "s1",
+    "s2",
+    "selector1",
+    "selector2",
+    "sendgrid",
+    "sig1",
+    "smtp",
+    "zendesk1",
+    "zoho",
+];
+
+/// The maximum amount of bytes read from an MTA-STS policy
+const MAX_POLICY_SIZE: u64 = 64 * 1024;
+
+#[derive(Debug, Default, Serialize)]
+pub struct Posture {
+    pub spf: Option<Spf>,
+    pub dmarc: Option<Dmarc>,
+    pub dkim: Vec<Dkim>,
+    pub mta_sts: Option<MtaSts>,
+    pub tls_rpt: Option<TlsRpt>,
+    pub bimi: Option<Bimi>,
+    pub spoofable: bool,
+}
+
+pub struct ModuleEmailSecurity {
+    config: config::EmailSecurityConfig,
+    processed_domains: Mutex<Vec<String>>,
+}
+
+impl ModuleEmailSecurity {
+    pub fn new(config: config::EmailSecurityConfig) -> Self {
+        ModuleEmailSecurity {
+            config,
+            processed_domains: Mutex::new(Vec::new()),
+        }
+    }
+
+    pub fn process(&self, domain: String) {
+        self.processed_domains.lock().unwrap().push(domain)
+    }
+
+    pub fn has_processed(&self, domain: String) -> bool {
+        self.processed_domains.lock().unwrap().contains(&domain)
+    }
+
+    fn get_dmarc(&self, session: &Session, domain: &str) -> Option<Dmarc> {
+        let resolver = session.get_resolver();
+        if let Some(dmarc) =
+            records::parse_dmarc(&resolver.lookup(&format!("_dmarc.{}", domain), RecordType::TXT))
+        {
+            return Some(dmarc);
+        }
+        // Subdomains without their own record are covered by the one of the organizational domain
+        let target = &session.get_args().domain;
+        if domain == target {
+            return None;
+        }
+        let mut dmarc =
+            records::parse_dmarc(&resolver.lookup(&format!("_dmarc.{}", target), RecordType::TXT))?;
+        dmarc.inherited_from = Some(target.clone());
+        Some(dmarc)
+    }
+
+    fn get_dkim(&self, session: &Session, domain: &str) -> Vec<Dkim> {
+        let selectors = self.config.dkim_selectors.clone().unwrap_or_else(|| {
+            DEFAULT_DKIM_SELECTORS
+                .iter()
+                .map(|selector| selector.to_string())
+                .collect()
+        });
+        let mut dkim = helpers::parallel::filter_map(selectors, 10, |selector| {
+            records::parse_dkim(
+                &selector,
+                &session.get_resolver().lookup(
+                    &format!("{}._domainkey.{}", selector, domain),
+                    RecordType::TXT,
+                ),
+            )
+        });
+        dkim.sort_by(|a, b| a.selector.cmp(&b.selector));
+        dkim
+    }
+
+    fn get_mta_sts(&self, session: &Session, domain: &str) -> Option<MtaSts> {
+        let id = records::parse_mta_sts_id(
+            &session
+                .get_resolver()
+                .lookup(&format!("_mta-sts.{}", domain), RecordType::TXT),
+        )?;
+        let mut mta_sts = MtaSts {
+            id: Some(id),
+            ..Default::default()
+        };
+        let response = session
+            .get_http_client()
+            .get(format!(
+                "https://mta-sts.{}/.well-known/mta-sts.txt",
+                domain
+            ))
+            .header(USER_AGENT, helpers::ua::get_random())
+            .send();
+        match response {
+            Ok(response) if response.status().is_success() => {
+                let mut policy = String::new();
+                let _ = response.take(MAX_POLICY_SIZE).read_to_string(&mut policy);
+                records::parse_mta_sts_policy(&mut mta_sts, &policy);
+            }
+            Ok(response) => {
+                mta_sts.error = Some(format!("The policy responded with {}", response.status()))
+            }
+            Err(e) => mta_sts.error = Some(format!("Failed fetching the policy: {}", e)),
+        }
+        Some(mta_sts)
+    }
+}
+
+/// Decides whether the domain can be spoofed and turns the weaknesses of its posture into findings
+pub fn evaluate(module: &str, posture: &Posture) -> (bool, Vec<Finding>) {
+    let mut findings = Vec::new();
+    let mut reasons = Vec::new();
+
+    match &posture.spf {
+        None => {
+            findings.push(Finding::new(
+                module,
+                Severity::Medium,
+                "No SPF record",
+                None,
+            ));
+        }
+        Some(spf) => {
+            match spf.all.as_deref() {
+                Some("+") => {
+                    reasons.push("the SPF record allows any sender");
+                    findings.push(Finding::new(
+                        module,
+                        Severity::High,
+                        "SPF record allows any sender (+all)",
+                        Some(spf.record.clone()),
+                    ));
+                }
+                Some("-") | Some("~") => {}
+                _ => findings.push(Finding::new(
+                    module,
+                    Severity::Low,
+                    "SPF record doesn't fail unauthorized senders",
+                    Some(spf.record.clone()),
+                )),
+            }
+            if !spf.errors.is_empty() {
+                findings.push(Finding::new(
+                    module,
+                    Severity::Medium,
+                    "SPF record is invalid",
+                    Some(spf.errors.join(", ")),
+                ));
+            }
+        }
+    }
+
+    match &posture.dmarc {
+        None => {
+            reasons.push("there's no DMARC record");
+            findings.push(Finding::new(
+                module,
+                Severity::Medium,
+                "No DMARC record",
+                None,
+            ));
+        }
+        Some(dmarc) => {
+            if !dmarc.is_enforced() {
+                reasons.push("the DMARC policy isn't enforced");
+            }
+            match dmarc.get_effective_policy() {
+                Some("reject") | Some("quarantine") => {}
+                _ => findings.push(Finding::new(
+                    module,
+                    Severity::Medium,
+                    "DMARC policy doesn't reject or quarantine failing messages",
+                    Some(dmarc.record.clone()),
+                )),
+            }
+            if dmarc.percentage < 100 {
+                findings.push(Finding::new(
+                    module,
+                    Severity::Low,
+                    format!(
+                        "DMARC policy only applies to {}% of the messages",
+                        dmarc.percentage
+                    ),
+                    Some(dmarc.record.clone()),
+                ));
+            }
+            if dmarc.aggregate_reports.is_empty() {
+                findings.push(Finding::new(
+                    module,
+                    Severity::Info,
+                    "DMARC record has no aggregate reporting address",
+                    Some(dmarc.record.clone()),
+                ));
+            }
+        }
+    }
+
+    if let Some(mode) = posture
+        .mta_sts
+        .as_ref()
+        .and_then(|mta_sts| mta_sts.mode.as_deref())
+        && mode != "enforce"
+    {
+        findings.push(Finding::new(
+            module,
+            Severity::Info,
+            "MTA-STS policy isn't enforced",
+            Some(format!("mode: {}", mode)),
+        ));
+    }
+
+    let spoofable = !reasons.is_empty();
+    if spoofable {
+        findings.push(Finding::new(
+            module,
+            Severity::High,
+            "Emails can be spoofed from this domain",
+            Some(reasons.join(", ")),
+        ));
+    }
+    (spoofable, findings)
+}
+
+impl Module for ModuleEmailSecurity {
+    fn name(&self) -> String {
+        String::from("email:security")
+    }
+
+    fn description(&self) -> String {
+        String::from(
+            "This module checks the email security records of a domain and whether emails can be spoofed from it",
+        )
+    }
+
+    fn subscribers(&self) -> Vec<events::Type> {
+        vec![events::Type::DiscoveredDomain(String::new())]
+    }
+
+    fn execute(&self, session: &Session, context: Context) -> Result<(), String> {
+        let domain = match context {
+            Context::Domain(domain) => domain,
+            _ => {
+                return Err("Received wrong context, exiting module".to_string());
+            }
+        };
+
+        if self.has_processed(domain.to_string()) {
+            return Ok(());
+        }
+        self.process(domain.to_string());
+
+        // Besides the target domain, only the domains receiving emails are checked
+        let resolver = session.get_resolver();
+        if domain != session.get_args().domain
+            && resolver.lookup(&domain, RecordType::MX).is_empty()
+        {
+            return Ok(());
+        }
+
+        let mut posture = Posture {
+            spf: spf::evaluate(&domain, &|name| resolver.lookup(name, RecordType::TXT)),
+            dmarc: self.get_dmarc(session, &domain),
+            dkim: self.get_dkim(session, &domain),
+            mta_sts: self.get_mta_sts(session, &domain),
+            tls_rpt: records::parse_tls_rpt(
+                &resolver.lookup(&format!("_smtp._tls.{}", domain), RecordType::TXT),
+            ),
+            bimi: records::parse_bimi(
+                &resolver.lookup(&format!("default._bimi.{}", domain), RecordType::TXT),
+            ),
+            spoofable: false,
+        };
+        let (spoofable, findings) = evaluate(&self.name(), &posture);
+        posture.spoofable = spoofable;
+        logger::println(
+            self.name(),
+            format!(
+                "Checked the email security of '{}'{}",
+                domain,
+                if spoofable {
+                    " $[fg:red]$[effect:bold](Spoofable)"
+                } else {
+                    ""
+                }
+            ),
+        );
+
+        if let Some(node) = session.get_database().search(Type::Domain, domain.clone()) {
+            if spoofable {
+                node.add_flag(flags::domain::SPOOFABLE);
+            }
+            node.add_data(
+                String::from("email"),
+                serde_json::to_value(&posture).map_err(|e| e.to_string())?,
+            );
+            for finding in findings {
+                node.add_finding(finding);
+            }
+        }
+
+        Ok(())
+    }
+}
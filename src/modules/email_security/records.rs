@@ -0,0 +1,178 @@
+use serde::Serialize;
+
+#[derive(Debug, Default, Serialize)]
+pub struct Dmarc {
+    pub record: String,
+    pub policy: Option<String>,
+    pub subdomain_policy: Option<String>,
+    /// The percentage of messages the policy applies to
+    pub percentage: u8,
+    pub aggregate_reports: Vec<String>,
+    pub forensic_reports: Vec<String>,
+    /// The domain the record was found on when it's inherited from the target domain
+    pub inherited_from: Option<String>,
+}
+
+impl Dmarc {
+    /// Returns the policy applying to the domain, which is the subdomain policy when inherited
+    pub fn get_effective_policy(&self) -> Option<&str> {
+        if self.inherited_from.is_some() {
+            self.subdomain_policy.as_deref().or(self.policy.as_deref())
+        } else {
+            self.policy.as_deref()
+        }
+    }
+
+    /// Whether messages failing the checks are rejected or quarantined
+    pub fn is_enforced(&self) -> bool {
+        matches!(
+            self.get_effective_policy(),
+            Some("reject") | Some("quarantine")
+        ) && self.percentage == 100
+    }
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct Dkim {
+    pub selector: String,
+    pub key_type: String,
+    /// A key is revoked by publishing an empty public key
+    pub revoked: bool,
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct MtaSts {
+    pub id: Option<String>,
+    pub mode: Option<String>,
+    pub mx: Vec<String>,
+    pub max_age: Option<u64>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct TlsRpt {
+    pub record: String,
+    pub reports: Vec<String>,
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct Bimi {
+    pub record: String,
+    pub logo: Option<String>,
+    pub certificate: Option<String>,
+}
+
+/// Parses a record made of `tag=value` pairs separated by semicolons, with lowercased tags
+pub fn parse_tags(record: &str) -> Vec<(String, String)> {
+    record
+        .split(';')
+        .filter_map(|tag| {
+            let (name, value) = tag.split_once('=')?;
+            Some((name.trim().to_lowercase(), value.trim().to_string()))
+        })
+        .collect()
+}
+
+fn get_tag<'a>(tags: &'a [(String, String)], name: &str) -> Option<&'a str> {
+    tags.iter()
+        .find(|(tag, _)| tag == name)
+        .map(|(_, value)| value.as_str())
+}
+
+fn split_list(value: Option<&str>) -> Vec<String> {
+    value
+        .map(|value| {
+            value
+                .split(',')
+                .map(|item| item.trim().to_string())
+                .filter(|item| !item.is_empty())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Finds the record starting with the given version tag, e.g. `v=DMARC1`
+fn find_record<'a>(txt_records: &'a [String], version: &str) -> Option<&'a String> {
+    txt_records.iter().find(|record| {
+        parse_tags(record)
+            .first()
+            .is_some_and(|(tag, value)| tag == "v" && value.eq_ignore_ascii_case(version))
+    })
+}
+
+pub fn parse_dmarc(txt_records: &[String]) -> Option<Dmarc> {
+    let record = find_record(txt_records, "DMARC1")?;
+    let tags = parse_tags(record);
+    Some(Dmarc {
+        record: record.clone(),
+        policy: get_tag(&tags, "p").map(|policy| policy.to_lowercase()),
+        subdomain_policy: get_tag(&tags, "sp").map(|policy| policy.to_lowercase()),
+        percentage: get_tag(&tags, "pct")
+            .and_then(|percentage| percentage.parse().ok())
+            .unwrap_or(100),
+        aggregate_reports: split_list(get_tag(&tags, "rua")),
+        forensic_reports: split_list(get_tag(&tags, "ruf")),
+        inherited_from: None,
+    })
+}
+
+pub fn parse_dkim(selector: &str, txt_records: &[String]) -> Option<Dkim> {
+    // The version tag is optional, the public key tag is not
+    let tags = txt_records
+        .iter()
+        .map(|record| parse_tags(record))
+        .find(|tags| get_tag(tags, "p").is_some())?;
+    Some(Dkim {
+        selector: selector.to_string(),
+        key_type: get_tag(&tags, "k").unwrap_or("rsa").to_lowercase(),
+        revoked: get_tag(&tags, "p").unwrap_or_default().is_empty(),
+    })
+}
+
+pub fn parse_mta_sts_id(txt_records: &[String]) -> Option<String> {
+    let record = find_record(txt_records, "STSv1")?;
+    Some(
+        get_tag(&parse_tags(record), "id")
+            .unwrap_or_default()
+            .to_string(),
+    )
+}
+
+/// Parses an MTA-STS policy, made of `key: value` lines
+pub fn parse_mta_sts_policy(mta_sts: &mut MtaSts, policy: &str) {
+    for line in policy.lines() {
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let value = value.trim().to_string();
+        match key.trim().to_lowercase().as_str() {
+            "mode" => mta_sts.mode = Some(value.to_lowercase()),
+            "mx" => mta_sts.mx.push(value),
+            "max_age" => mta_sts.max_age = value.parse().ok(),
+            _ => {}
+        }
+    }
+}
+
+pub fn parse_tls_rpt(txt_records: &[String]) -> Option<TlsRpt> {
+    let record = find_record(txt_records, "TLSRPTv1")?;
+    Some(TlsRpt {
+        record: record.clone(),
+        reports: split_list(get_tag(&parse_tags(record), "rua")),
+    })
+}
+
+pub fn parse_bimi(txt_records: &[String]) -> Option<Bimi> {
+    let record = find_record(txt_records, "BIMI1")?;
+    let tags = parse_tags(record);
+    let non_empty = |value: Option<&str>| {
+        value
+            .filter(|value| !value.is_empty())
+            .map(|value| value.to_string())
+    };
+    Some(Bimi {
+        record: record.clone(),
+        logo: non_empty(get_tag(&tags, "l")),
+        certificate: non_empty(get_tag(&tags, "a")),
+    })
+}
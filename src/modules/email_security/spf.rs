@@ -0,0 +1,134 @@
+use serde::Serialize;
+
+/// The maximum amount of DNS lookups an SPF evaluation may cause, as defined by RFC 7208
+pub const MAX_LOOKUPS: usize = 10;
+
+/// How deep includes and redirects are followed, which protects against loops along with the visited domains
+const MAX_DEPTH: usize = 10;
+
+#[derive(Debug, Default, Serialize)]
+pub struct Spf {
+    pub record: String,
+    /// The qualifier of the `all` mechanism that applies, following redirects, or `None` if there's none
+    pub all: Option<String>,
+    /// The amount of DNS lookups needed to evaluate the record and the ones it includes
+    pub lookups: usize,
+    pub includes: Vec<String>,
+    pub errors: Vec<String>,
+}
+
+/// Returns the SPF records among the TXT records of a domain
+pub fn find_records(txt_records: &[String]) -> Vec<String> {
+    txt_records
+        .iter()
+        .filter(|record| {
+            let record = record.to_lowercase();
+            record == "v=spf1" || record.starts_with("v=spf1 ")
+        })
+        .cloned()
+        .collect()
+}
+
+/// Splits a term into its qualifier, its name and its value, e.g. `~include:_spf.google.com`
+fn parse_term(term: &str) -> (String, String, Option<String>) {
+    let (qualifier, term) = match term.chars().next() {
+        Some(qualifier @ ('+' | '-' | '~' | '?')) => (qualifier.to_string(), &term[1..]),
+        _ => (String::from("+"), term),
+    };
+    match term.find([':', '=']) {
+        Some(index) => (
+            qualifier,
+            term[..index].to_lowercase(),
+            Some(term[index + 1..].to_string()),
+        ),
+        None => (qualifier, term.to_lowercase(), None),
+    }
+}
+
+/// Parses the SPF record of a domain and the ones it includes or redirects to, `fetch` returning the TXT records of a domain
+pub fn evaluate(domain: &str, fetch: &dyn Fn(&str) -> Vec<String>) -> Option<Spf> {
+    let records = find_records(&fetch(domain));
+    let record = records.first()?.clone();
+    let mut spf = Spf {
+        record: record.clone(),
+        ..Default::default()
+    };
+    if records.len() > 1 {
+        spf.errors
+            .push(format!("Multiple SPF records found for '{}'", domain));
+    }
+    let mut visited = vec![domain.to_lowercase()];
+    spf.all = expand(&record, fetch, &mut spf, &mut visited, 0);
+    if spf.lookups > MAX_LOOKUPS {
+        spf.errors.push(format!(
+            "{} DNS lookups are needed while at most {} are allowed",
+            spf.lookups, MAX_LOOKUPS
+        ));
+    }
+    Some(spf)
+}
+
+/// Counts the lookups of a record and follows its includes, returning the qualifier of the `all` mechanism that applies to it
+fn expand(
+    record: &str,
+    fetch: &dyn Fn(&str) -> Vec<String>,
+    spf: &mut Spf,
+    visited: &mut Vec<String>,
+    depth: usize,
+) -> Option<String> {
+    let mut all = None;
+    let mut redirect = None;
+    for term in record.split_whitespace().skip(1) {
+        let (qualifier, name, value) = parse_term(term);
+        match name.as_str() {
+            "all" => all = Some(qualifier),
+            "a" | "mx" | "ptr" | "exists" => spf.lookups += 1,
+            "include" => {
+                spf.lookups += 1;
+                let Some(target) = value else {
+                    spf.errors.push(String::from("Include without a domain"));
+                    continue;
+                };
+                if !spf.includes.contains(&target) {
+                    spf.includes.push(target.clone());
+                }
+                follow(&target, fetch, spf, visited, depth);
+            }
+            "redirect" => {
+                spf.lookups += 1;
+                redirect = value;
+            }
+            _ => {}
+        }
+    }
+
+    // A redirect is ignored when the record has an `all` mechanism
+    match (all, redirect) {
+        (Some(all), _) => Some(all),
+        (None, Some(target)) => follow(&target, fetch, spf, visited, depth),
+        (None, None) => None,
+    }
+}
+
+fn follow(
+    target: &str,
+    fetch: &dyn Fn(&str) -> Vec<String>,
+    spf: &mut Spf,
+    visited: &mut Vec<String>,
+    depth: usize,
+) -> Option<String> {
+    let target = target.to_lowercase();
+    // Macros are expanded at evaluation time and can't be followed
+    if target.contains('%') || depth >= MAX_DEPTH || visited.contains(&target) {
+        return None;
+    }
+    visited.push(target.clone());
+    match find_records(&fetch(&target)).first() {
+        Some(record) => expand(record, fetch, spf, visited, depth + 1),
+        None => {
+            spf.errors
+                .push(format!("No SPF record found for '{}'", target));
+            None
+        }
+    }
+}
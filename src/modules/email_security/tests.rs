@@ -0,0 +1,85 @@
+use std::collections::HashMap;
+
+use crate::modules::email_security::records::{parse_dkim, parse_dmarc};
+use crate::modules::email_security::spf::{self, MAX_LOOKUPS};
+use crate::modules::email_security::{Posture, evaluate};
+
+fn records(records: &[&str]) -> Vec<String> {
+    records.iter().map(|record| record.to_string()).collect()
+}
+
+fn fetch(zone: &HashMap<&str, &str>) -> impl Fn(&str) -> Vec<String> {
+    move |domain| {
+        zone.get(domain)
+            .map(|record| records(&[record]))
+            .unwrap_or_default()
+    }
+}
+
+#[test]
+fn evaluate_spf_includes_and_redirects() {
+    let zone = HashMap::from([
+        (
+            "krypton.ninja",
+            "v=spf1 mx include:_spf.krypton.ninja redirect=_all.krypton.ninja",
+        ),
+        (
+            "_spf.krypton.ninja",
+            "v=spf1 ip4:192.0.2.0/24 include:krypton.ninja ?all",
+        ),
+        ("_all.krypton.ninja", "v=spf1 a -all"),
+    ]);
+    let spf = spf::evaluate("krypton.ninja", &fetch(&zone)).unwrap();
+    assert_eq!(spf.all.as_deref(), Some("-"));
+    assert_eq!(spf.lookups, 5);
+    assert_eq!(spf.includes, vec!["_spf.krypton.ninja", "krypton.ninja"]);
+    assert!(spf.errors.is_empty());
+    assert!(spf::evaluate("go.krypton.ninja", &fetch(&zone)).is_none());
+}
+
+#[test]
+fn evaluate_spf_lookup_limit() {
+    let record = format!("v=spf1 {} ~all", ["a"; MAX_LOOKUPS + 1].join(" "));
+    let zone = HashMap::from([("krypton.ninja", record.as_str())]);
+    let spf = spf::evaluate("krypton.ninja", &fetch(&zone)).unwrap();
+    assert_eq!(spf.lookups, MAX_LOOKUPS + 1);
+    assert_eq!(spf.errors.len(), 1);
+}
+
+#[test]
+fn parse_dmarc_record() {
+    let dmarc = parse_dmarc(&records(&[
+        "google-site-verification=abc",
+        "v=DMARC1; p=Quarantine; pct=50; rua=mailto:a@krypton.ninja,mailto:b@krypton.ninja",
+    ]))
+    .unwrap();
+    assert_eq!(dmarc.policy.as_deref(), Some("quarantine"));
+    assert_eq!(dmarc.percentage, 50);
+    assert_eq!(dmarc.aggregate_reports.len(), 2);
+    assert!(!dmarc.is_enforced());
+    assert!(parse_dmarc(&records(&["v=spf1 -all"])).is_none());
+}
+
+#[test]
+fn parse_revoked_dkim_key() {
+    let dkim = parse_dkim("google", &records(&["v=DKIM1; k=rsa; p="])).unwrap();
+    assert!(dkim.revoked);
+    assert!(parse_dkim("google", &records(&["v=spf1 -all"])).is_none());
+}
+
+#[test]
+fn evaluate_spoofable_posture() {
+    let zone = HashMap::from([("krypton.ninja", "v=spf1 -all")]);
+    let mut posture = Posture {
+        spf: spf::evaluate("krypton.ninja", &fetch(&zone)),
+        ..Default::default()
+    };
+    assert!(evaluate("email:security", &posture).0);
+
+    posture.dmarc = parse_dmarc(&records(&[
+        "v=DMARC1; p=reject; rua=mailto:a@krypton.ninja",
+    ]));
+    let (spoofable, findings) = evaluate("email:security", &posture);
+    assert!(!spoofable);
+    assert!(findings.is_empty());
+}
@@ -0,0 +1,49 @@
+use std::hash::{DefaultHasher, Hash, Hasher};
+
+/// How much the size of a response may differ from a baseline reflecting the path, which depends on the length of the path
+const SIZE_TOLERANCE: usize = 64;
+
+/// What identifies a response when comparing it to the ones served for paths that don't exist
+#[derive(Debug, Clone, PartialEq)]
+pub struct Fingerprint {
+    pub status: u16,
+    pub size: usize,
+    pub words: usize,
+    pub hash: u64,
+    /// Whether the body contains the requested path
+    pub reflects_path: bool,
+}
+
+impl Fingerprint {
+    pub fn new(status: u16, body: &[u8], path: &str) -> Self {
+        let mut hasher = DefaultHasher::new();
+        body.hash(&mut hasher);
+        let body_text = String::from_utf8_lossy(body);
+        Fingerprint {
+            status,
+            size: body.len(),
+            words: body_text.split_whitespace().count(),
+            hash: hasher.finish(),
+            reflects_path: !path.is_empty() && body_text.contains(path),
+        }
+    }
+
+    /// Whether the response looks like the one served for a path that doesn't exist, whose content may reflect the path
+    pub fn matches(&self, baseline: &Fingerprint) -> bool {
+        if self.status != baseline.status {
+            return false;
+        }
+        if baseline.reflects_path {
+            return self.words == baseline.words
+                && self.size.abs_diff(baseline.size) <= SIZE_TOLERANCE;
+        }
+        self.hash == baseline.hash || self.size == baseline.size
+    }
+}
+
+/// Whether the response matches any of the responses served for random paths
+pub fn is_soft_404(baselines: &[Fingerprint], fingerprint: &Fingerprint) -> bool {
+    baselines
+        .iter()
+        .any(|baseline| fingerprint.matches(baseline))
+}
@@ -0,0 +1,215 @@
+use std::collections::HashSet;
+use std::io::Read;
+use std::sync::{Mutex, OnceLock};
+
+use reqwest::blocking::Client;
+use reqwest::header::USER_AGENT;
+
+use crate::database::node::{Node, Type};
+use crate::modules::http_bruteforce::calibration::Fingerprint;
+use crate::modules::{Context, Module};
+use crate::session::Session;
+use crate::{config, events, helpers, logger};
+
+mod calibration;
+#[cfg(test)]
+mod tests;
+
+/// The maximum amount of bytes read from a response body
+const MAX_BODY_SIZE: u64 = 1024 * 1024;
+
+/// The status codes considered as found when none are configured
+const DEFAULT_STATUS_CODES: [u16; 9] = [200, 204, 301, 302, 307, 401, 403, 405, 500];
+
+pub struct ModuleHttpBruteforce {
+    config: config::HttpBruteforceConfig,
+    client: Client,
+    paths: OnceLock<Vec<String>>,
+    processed_urls: Mutex<Vec<String>>,
+}
+
+impl ModuleHttpBruteforce {
+    pub fn new(config: config::HttpBruteforceConfig) -> Result<Self, String> {
+        let client = helpers::http::client(config.timeout)?;
+        Ok(ModuleHttpBruteforce {
+            config,
+            client,
+            paths: OnceLock::new(),
+            processed_urls: Mutex::new(Vec::new()),
+        })
+    }
+
+    pub fn process(&self, url: String) {
+        self.processed_urls.lock().unwrap().push(url)
+    }
+
+    pub fn has_processed(&self, url: String) -> bool {
+        self.processed_urls.lock().unwrap().contains(&url)
+    }
+
+    fn get_extensions(&self) -> Vec<String> {
+        self.config
+            .extensions
+            .clone()
+            .unwrap_or_default()
+            .iter()
+            .map(|extension| extension.trim_start_matches('.').to_string())
+            .filter(|extension| !extension.is_empty())
+            .collect()
+    }
+
+    fn get_paths(&self, session: &Session) -> Result<&Vec<String>, String> {
+        if let Some(paths) = self.paths.get() {
+            return Ok(paths);
+        }
+        let path = self
+            .config
+            .wordlist
+            .clone()
+            .unwrap_or(session.get_args().wordlist.clone());
+        let words = helpers::wordlist::read(&path)?;
+        Ok(self
+            .paths
+            .get_or_init(|| get_paths(&words, &self.get_extensions())))
+    }
+
+    fn fetch(&self, base_url: &str, path: &str) -> Option<Fingerprint> {
+        let response = self
+            .client
+            .get(format!("{}/{}", base_url, path))
+            .header(USER_AGENT, helpers::ua::get_random())
+            .send()
+            .ok()?;
+        let status = response.status().as_u16();
+        let mut body = Vec::new();
+        let _ = response.take(MAX_BODY_SIZE).read_to_end(&mut body);
+        Some(Fingerprint::new(status, &body, path))
+    }
+
+    /// Fetches random paths of every shape that is brute-forced, to know what the server answers for paths that don't exist
+    fn calibrate(&self, base_url: &str) -> Vec<Fingerprint> {
+        let random = || format!("{:016x}", rand::random::<u64>());
+        let mut paths = vec![random(), format!("{}/", random()), format!(".{}", random())];
+        for extension in self.get_extensions() {
+            paths.push(format!("{}.{}", random(), extension));
+        }
+        let mut baselines = Vec::new();
+        for path in paths {
+            if let Some(fingerprint) = self.fetch(base_url, &path)
+                && !baselines.contains(&fingerprint)
+            {
+                baselines.push(fingerprint);
+            }
+        }
+        baselines
+    }
+}
+
+/// Returns the paths to request from the words of a wordlist, each word being tried with every extension as well
+pub fn get_paths(words: &[String], extensions: &[String]) -> Vec<String> {
+    let mut paths = Vec::new();
+    for word in words {
+        let word = word.trim_start_matches('/');
+        if word.is_empty() || word.contains(char::is_whitespace) {
+            continue;
+        }
+        paths.push(word.to_string());
+        if !word.ends_with('/') {
+            for extension in extensions {
+                paths.push(format!("{}.{}", word, extension));
+            }
+        }
+    }
+    let mut seen = HashSet::new();
+    paths.retain(|path| seen.insert(path.clone()));
+    paths
+}
+
+impl Module for ModuleHttpBruteforce {
+    fn name(&self) -> String {
+        String::from("http:bruteforce")
+    }
+
+    fn description(&self) -> String {
+        String::from(
+            "This module will discover the content of the web servers of a domain by requesting the entries of a wordlist",
+        )
+    }
+
+    fn subscribers(&self) -> Vec<events::Type> {
+        vec![events::Type::LiveHttpHost(String::new(), String::new())]
+    }
+
+    fn execute(&self, session: &Session, context: Context) -> Result<(), String> {
+        let (domain, base_url) = match context {
+            Context::HttpHost(domain, base_url) => (domain, base_url),
+            _ => {
+                return Err("Received wrong context, exiting module".to_string());
+            }
+        };
+
+        if self.has_processed(base_url.clone()) {
+            return Ok(());
+        }
+        self.process(base_url.clone());
+
+        let paths = self.get_paths(session)?;
+        let baselines = self.calibrate(&base_url);
+        if baselines.is_empty() {
+            return Err(format!("Failed calibrating '{}', skipping it", base_url));
+        }
+        if session.get_state().is_debug_or_verbose() {
+            logger::info(
+                self.name(),
+                format!(
+                    "Calibrated '{}' with {} responses for missing paths",
+                    base_url,
+                    baselines.len()
+                ),
+            );
+        }
+
+        let status_codes = self
+            .config
+            .status_codes
+            .clone()
+            .unwrap_or(DEFAULT_STATUS_CODES.to_vec());
+        let concurrency = self.config.concurrency.unwrap_or(20);
+        let mut hits = helpers::parallel::filter_map(paths.clone(), concurrency, |path| {
+            let fingerprint = self.fetch(&base_url, &path)?;
+            if !status_codes.contains(&fingerprint.status)
+                || calibration::is_soft_404(&baselines, &fingerprint)
+            {
+                return None;
+            }
+            Some((format!("{}/{}", base_url, path), fingerprint))
+        });
+        if hits.is_empty() {
+            return Ok(());
+        }
+        hits.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+        let mut database = session.get_database();
+        for (url, fingerprint) in hits {
+            logger::println(
+                self.name(),
+                format!(
+                    "Found '{}' with status {} $[fg:gray]({} bytes)",
+                    url, fingerprint.status, fingerprint.size
+                ),
+            );
+            // Other modules may have found the URL already, without requesting it
+            if let Some(url_node) = database.search(Type::Url, url.clone()) {
+                url_node.add_data(String::from("status"), fingerprint.status.into());
+                url_node.add_data(String::from("size"), fingerprint.size.into());
+            } else if let Some(node) = database.search(Type::Domain, domain.clone()) {
+                let mut url_node = Node::new(Type::Url, url);
+                url_node.add_data(String::from("status"), fingerprint.status.into());
+                url_node.add_data(String::from("size"), fingerprint.size.into());
+                node.connect(url_node);
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,74 @@
+use crate::modules::http_bruteforce::calibration::{Fingerprint, is_soft_404};
+use crate::modules::http_bruteforce::get_paths;
+
+#[test]
+fn build_paths() {
+    let words = ["admin", "/login", "backup/", "# comment here", "admin"]
+        .iter()
+        .map(|word| word.to_string())
+        .collect::<Vec<String>>();
+    assert_eq!(
+        get_paths(&words, &[String::from("php")]),
+        vec!["admin", "admin.php", "login", "login.php", "backup/"]
+    );
+}
+
+#[test]
+fn filter_soft_404() {
+    let baselines = vec![
+        Fingerprint::new(404, b"<h1>Not Found</h1>", "a1b2c3d4e5f6a7b8"),
+        Fingerprint::new(
+            200,
+            b"<html>Sorry, /a1b2c3d4e5f6a7b8 doesn't exist on this site</html>",
+            "a1b2c3d4e5f6a7b8",
+        ),
+    ];
+    assert!(baselines[1].reflects_path);
+
+    // The same page reflecting another path has another size and hash, but the same amount of words
+    let reflected = Fingerprint::new(
+        200,
+        b"<html>Sorry, /admin doesn't exist on this site</html>",
+        "admin",
+    );
+    assert!(is_soft_404(&baselines, &reflected));
+    assert!(is_soft_404(
+        &baselines,
+        &Fingerprint::new(404, b"<h1>Not Found</h1>", "admin")
+    ));
+
+    let found = Fingerprint::new(
+        200,
+        b"<html><title>Admin</title><form>Log in</form></html>",
+        "admin",
+    );
+    assert!(!is_soft_404(&baselines, &found));
+    let redirect = Fingerprint::new(301, b"", "admin");
+    assert!(!is_soft_404(&baselines, &redirect));
+}
+
+#[test]
+fn keep_pages_with_as_many_words() {
+    let baselines = vec![Fingerprint::new(
+        200,
+        b"<html><h1>Welcome</h1><p>Home</p></html>",
+        "a1b2c3d4e5f6a7b8",
+    )];
+    assert!(!baselines[0].reflects_path);
+
+    // A page without the path and as many words is only a soft 404 with the same hash or size
+    let found = Fingerprint::new(200, b"<html><h1>Dashboard</h1><p>Login</p></html>", "admin");
+    assert!(!is_soft_404(&baselines, &found));
+
+    let reflected = Fingerprint::new(
+        200,
+        b"<html>Nothing at /a1b2c3d4e5f6a7b8</html>",
+        "a1b2c3d4e5f6a7b8",
+    );
+    let large = Fingerprint::new(
+        200,
+        format!("<html>Nothing at {}</html>", "x".repeat(4096)).as_bytes(),
+        "admin",
+    );
+    assert!(!is_soft_404(&[reflected], &large));
+}
@@ -0,0 +1,305 @@
+use std::collections::VecDeque;
+use std::io::Read;
+use std::sync::{LazyLock, Mutex};
+
+use regex::Regex;
+use reqwest::Url;
+use reqwest::blocking::Client;
+use reqwest::header::{CONTENT_TYPE, LOCATION, USER_AGENT};
+
+use crate::database::node::{Node, Type};
+use crate::modules::http_crawler::rate::RateLimiter;
+use crate::modules::{Context, Module};
+use crate::session::Session;
+use crate::{config, events, helpers, logger};
+
+mod rate;
+#[cfg(test)]
+mod tests;
+
+/// The maximum amount of redirects followed on the same web server
+const MAX_REDIRECTS: usize = 5;
+
+/// The maximum amount of bytes read from a page
+const MAX_BODY_SIZE: u64 = 1024 * 1024;
+
+/// The extensions of the links that aren't worth requesting
+const STATIC_EXTENSIONS: [&str; 20] = [
+    ".css", ".png", ".jpg", ".jpeg", ".gif", ".svg", ".ico", ".webp", ".bmp", ".woff", ".woff2",
+    ".ttf", ".eot", ".otf", ".mp3", ".mp4", ".webm", ".avi", ".pdf", ".zip",
+];
+
+static LINK: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r#"(?i)\b(?:href|src|action|formaction|data-src)\s*=\s*(?:"([^"]*)"|'([^']*)'|([^\s"'>]+))"#,
+    )
+    .unwrap()
+});
+
+static EMAIL: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)\b[a-z0-9][a-z0-9.+!%\-]{0,63}@((?:[a-z0-9](?:[a-z0-9-]{0,61}[a-z0-9])?\.)+[a-z][a-z0-9-]{0,62})").unwrap()
+});
+
+/// A page requested while crawling
+struct Page {
+    url: String,
+    status: u16,
+    size: usize,
+}
+
+pub struct ModuleHttpCrawler {
+    config: config::HttpCrawlerConfig,
+    client: Client,
+    rate_limiter: RateLimiter,
+    processed_urls: Mutex<Vec<String>>,
+}
+
+impl ModuleHttpCrawler {
+    pub fn new(config: config::HttpCrawlerConfig) -> Result<Self, String> {
+        // Redirects are followed manually, so that other web servers aren't requested and every request is rate limited
+        let client = helpers::http::client(config.timeout)?;
+        let rate_limiter = RateLimiter::new(config.rate.unwrap_or(10));
+        Ok(ModuleHttpCrawler {
+            config,
+            client,
+            rate_limiter,
+            processed_urls: Mutex::new(Vec::new()),
+        })
+    }
+
+    pub fn process(&self, url: String) {
+        self.processed_urls.lock().unwrap().push(url)
+    }
+
+    pub fn has_processed(&self, url: String) -> bool {
+        self.processed_urls.lock().unwrap().contains(&url)
+    }
+
+    /// Fetches the page, following the redirects staying on the same web server, returning the page and its content when it is HTML served by that web server
+    fn fetch(&self, url: &Url) -> Option<(Page, Option<String>)> {
+        let mut current = url.clone();
+        let mut redirects = 0;
+        let response = loop {
+            self.rate_limiter
+                .wait(current.host_str().unwrap_or_default());
+            let response = self
+                .client
+                .get(current.clone())
+                .header(USER_AGENT, helpers::ua::get_random())
+                .send()
+                .ok()?;
+            let location = response
+                .headers()
+                .get(LOCATION)
+                .and_then(|location| location.to_str().ok())
+                .and_then(|location| current.join(location).ok());
+            // Pages redirecting to another web server are crawled from that web server, if it is one of the target
+            match location {
+                Some(location)
+                    if response.status().is_redirection()
+                        && location.origin() == url.origin()
+                        && redirects < MAX_REDIRECTS =>
+                {
+                    redirects += 1;
+                    current = location;
+                }
+                _ => break response,
+            }
+        };
+        let status = response.status().as_u16();
+        let is_same_origin = !response.status().is_redirection();
+        let is_html = response
+            .headers()
+            .get(CONTENT_TYPE)
+            .and_then(|content_type| content_type.to_str().ok())
+            .is_some_and(|content_type| content_type.contains("html"));
+        let mut content = Vec::new();
+        let _ = response.take(MAX_BODY_SIZE).read_to_end(&mut content);
+        let page = Page {
+            url: url.to_string(),
+            status,
+            size: content.len(),
+        };
+        let content =
+            (is_same_origin && is_html).then(|| String::from_utf8_lossy(&content).to_string());
+        Some((page, content))
+    }
+}
+
+/// Returns the URLs of the links, forms and resources of a page, without their fragment
+pub fn get_links(html: &str, page_url: &Url) -> Vec<Url> {
+    let mut links = Vec::new();
+    for captures in LINK.captures_iter(html) {
+        let Some(link) = captures
+            .get(1)
+            .or_else(|| captures.get(2))
+            .or_else(|| captures.get(3))
+        else {
+            continue;
+        };
+        let link = link.as_str().trim().replace("&amp;", "&");
+        if link.is_empty() || link.starts_with('#') {
+            continue;
+        }
+        let Ok(mut url) = page_url.join(&link) else {
+            continue;
+        };
+        if !["http", "https"].contains(&url.scheme()) {
+            continue;
+        }
+        url.set_fragment(None);
+        let path = url.path().to_lowercase();
+        if STATIC_EXTENSIONS
+            .iter()
+            .any(|extension| path.ends_with(extension))
+        {
+            continue;
+        }
+        if !links.contains(&url) {
+            links.push(url);
+        }
+    }
+    links
+}
+
+/// Returns the emails of the target domain and its subdomains found in a page
+pub fn get_emails(html: &str, target: &str) -> Vec<String> {
+    let mut emails = Vec::new();
+    for captures in EMAIL.captures_iter(html) {
+        let found = captures.get(0).unwrap();
+        let domain = captures[1].to_lowercase();
+        if (domain != target && !domain.ends_with(&format!(".{}", target)))
+            || helpers::dns::continues_hostname(&html[found.end()..])
+        {
+            continue;
+        }
+        let email = found.as_str().to_lowercase();
+        if !emails.contains(&email) {
+            emails.push(email);
+        }
+    }
+    emails
+}
+
+impl Module for ModuleHttpCrawler {
+    fn name(&self) -> String {
+        String::from("http:crawler")
+    }
+
+    fn description(&self) -> String {
+        String::from(
+            "This module will crawl the web servers of a domain to find their pages, the subdomains they link to and the emails they show",
+        )
+    }
+
+    fn subscribers(&self) -> Vec<events::Type> {
+        vec![events::Type::LiveHttpHost(String::new(), String::new())]
+    }
+
+    fn execute(&self, session: &Session, context: Context) -> Result<(), String> {
+        let (domain, base_url) = match context {
+            Context::HttpHost(domain, base_url) => (domain, base_url),
+            _ => {
+                return Err("Received wrong context, exiting module".to_string());
+            }
+        };
+
+        if self.has_processed(base_url.clone()) {
+            return Ok(());
+        }
+        self.process(base_url.clone());
+
+        let Ok(start) = Url::parse(&format!("{}/", base_url)) else {
+            return Ok(());
+        };
+        let max_depth = self.config.depth.unwrap_or(2);
+        let max_pages = self.config.max_pages.unwrap_or(100);
+        let target = &session.get_args().domain;
+
+        let mut queue = VecDeque::from([(start.clone(), 0)]);
+        let mut queued = vec![start.clone()];
+        let mut pages = Vec::new();
+        let mut hosts = Vec::new();
+        let mut emails = Vec::new();
+        let mut requests = 0;
+        while let Some((url, depth)) = queue.pop_front() {
+            if requests >= max_pages {
+                break;
+            }
+            requests += 1;
+            let Some((page, content)) = self.fetch(&url) else {
+                continue;
+            };
+            // Broken links aren't pages of the web server
+            if page.status != 404 {
+                pages.push(page);
+            }
+            let Some(content) = content else {
+                continue;
+            };
+
+            for email in get_emails(&content, target) {
+                if !emails.contains(&email) {
+                    emails.push(email);
+                }
+            }
+            for link in get_links(&content, &url) {
+                let Some(host) = link.host_str().map(|host| host.to_lowercase()) else {
+                    continue;
+                };
+                if !session.is_in_scope(&host) {
+                    continue;
+                }
+                if link.origin() != start.origin() {
+                    if host != domain && &host != target && !hosts.contains(&host) {
+                        hosts.push(host);
+                    }
+                    continue;
+                }
+                if depth < max_depth && !queued.contains(&link) {
+                    queued.push(link.clone());
+                    queue.push_back((link, depth + 1));
+                }
+            }
+        }
+
+        if session.get_state().is_debug_or_verbose() {
+            logger::info(
+                self.name(),
+                format!("Crawled {} pages of '{}'", requests, base_url),
+            );
+        }
+
+        hosts.sort();
+        for host in hosts {
+            session.discover_domain(&self.name(), host, None);
+        }
+
+        let mut database = session.get_database();
+        let mut added = 0;
+        for page in pages {
+            if database.search(Type::Url, page.url.clone()).is_none()
+                && let Some(node) = database.search(Type::Domain, domain.clone())
+            {
+                let mut url_node = Node::new(Type::Url, page.url);
+                url_node.add_data(String::from("status"), page.status.into());
+                url_node.add_data(String::from("size"), page.size.into());
+                node.connect(url_node);
+                added += 1;
+            }
+        }
+        if added > 0 {
+            logger::println(
+                self.name(),
+                format!("Found {} URLs on '{}'", added, base_url),
+            );
+        }
+
+        drop(database);
+        for email in emails {
+            session.discover_email(&self.name(), email, &domain);
+        }
+
+        Ok(())
+    }
+}
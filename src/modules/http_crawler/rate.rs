@@ -0,0 +1,34 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Spaces out the requests sent to each host, whichever web server of the host they are for
+pub struct RateLimiter {
+    interval: Duration,
+    next_requests: Mutex<HashMap<String, Instant>>,
+}
+
+impl RateLimiter {
+    pub fn new(requests_per_second: u32) -> Self {
+        RateLimiter {
+            interval: Duration::from_secs(1) / requests_per_second.max(1),
+            next_requests: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Waits until a request can be sent to the host
+    pub fn wait(&self, host: &str) {
+        let now = Instant::now();
+        let scheduled = {
+            let mut next_requests = self.next_requests.lock().unwrap();
+            let scheduled = next_requests
+                .get(host)
+                .map(|next| (*next).max(now))
+                .unwrap_or(now);
+            next_requests.insert(host.to_string(), scheduled + self.interval);
+            scheduled
+        };
+        thread::sleep(scheduled.saturating_duration_since(now));
+    }
+}
@@ -0,0 +1,50 @@
+use std::time::{Duration, Instant};
+
+use reqwest::Url;
+
+use crate::modules::http_crawler::rate::RateLimiter;
+use crate::modules::http_crawler::{get_emails, get_links};
+
+#[test]
+fn collect_links() {
+    let html = r##"<a href="/about#team">About</a>
+<a href='contact?ref=home&amp;lang=en'>Contact</a>
+<form action=/login method="post"></form>
+<img src="/logo.png"><link href="/style.css" rel="stylesheet">
+<a href="mailto:hello@example.com">Mail</a><a href="#top">Top</a>
+<a href="https://blog.example.com/">Blog</a><a href="/about">About</a>"##;
+    let links = get_links(html, &Url::parse("https://example.com/home/").unwrap());
+    assert_eq!(
+        links.iter().map(|url| url.as_str()).collect::<Vec<&str>>(),
+        vec![
+            "https://example.com/about",
+            "https://example.com/home/contact?ref=home&lang=en",
+            "https://example.com/login",
+            "https://blog.example.com/",
+        ]
+    );
+}
+
+#[test]
+fn collect_emails() {
+    let html = "Write to <a href=\"mailto:Jobs@Example.com\">us</a>, support@help.example.com or info@other.com. jobs@example.com, admin@example.com.evil.net";
+    assert_eq!(
+        get_emails(html, "example.com"),
+        vec!["jobs@example.com", "support@help.example.com"]
+    );
+}
+
+#[test]
+fn limit_rate_per_host() {
+    let rate_limiter = RateLimiter::new(20);
+    let start = Instant::now();
+    for _ in 0..3 {
+        rate_limiter.wait("example.com");
+    }
+    assert!(start.elapsed() >= Duration::from_millis(100));
+
+    // Other hosts don't wait for the requests sent to the first one
+    let start = Instant::now();
+    rate_limiter.wait("other.example.com");
+    assert!(start.elapsed() < Duration::from_millis(50));
+}
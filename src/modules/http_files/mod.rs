@@ -0,0 +1,172 @@
+use std::io::Read;
+use std::ops::Range;
+use std::sync::Mutex;
+
+use reqwest::blocking::Client;
+use reqwest::header::USER_AGENT;
+
+use crate::database::node::Type;
+use crate::findings::Finding;
+use crate::modules::http_files::paths::SensitiveFile;
+use crate::modules::{Context, Module};
+use crate::session::Session;
+use crate::{config, events, helpers, logger};
+
+mod paths;
+#[cfg(test)]
+mod tests;
+
+/// The maximum amount of bytes read from a file when looking for its signatures
+const MAX_CONTENT_SIZE: u64 = 64 * 1024;
+
+/// The maximum length of the evidence saved with a finding
+const MAX_SNIPPET_LENGTH: usize = 120;
+
+pub struct ModuleHttpFiles {
+    config: config::HttpFilesConfig,
+    client: Client,
+    files: Vec<SensitiveFile>,
+    processed_urls: Mutex<Vec<String>>,
+}
+
+impl ModuleHttpFiles {
+    pub fn new(config: config::HttpFilesConfig) -> Result<Self, String> {
+        let client = helpers::http::client(config.timeout)?;
+        let files = paths::compile(helpers::entries::load(
+            paths::DEFAULT_PATHS,
+            config.paths.as_deref(),
+            "paths",
+        )?)?;
+        Ok(ModuleHttpFiles {
+            config,
+            client,
+            files,
+            processed_urls: Mutex::new(Vec::new()),
+        })
+    }
+
+    pub fn process(&self, url: String) {
+        self.processed_urls.lock().unwrap().push(url)
+    }
+
+    pub fn has_processed(&self, url: String) -> bool {
+        self.processed_urls.lock().unwrap().contains(&url)
+    }
+
+    /// Fetches the status code and the beginning of the content served at the URL
+    fn fetch(&self, url: &str) -> Option<(u16, Vec<u8>)> {
+        let response = self
+            .client
+            .get(url)
+            .header(USER_AGENT, helpers::ua::get_random())
+            .send()
+            .ok()?;
+        let status = response.status().as_u16();
+        let mut content = Vec::new();
+        let _ = response.take(MAX_CONTENT_SIZE).read_to_end(&mut content);
+        Some((status, content))
+    }
+}
+
+/// Returns the line of the content where a signature was found, with the non-printable characters replaced by dots
+pub fn get_snippet(content: &[u8], range: Range<usize>) -> String {
+    let start = content[..range.start]
+        .iter()
+        .rposition(|byte| *byte == b'\n')
+        .map(|position| position + 1)
+        .unwrap_or(0);
+    let end = content[range.start..]
+        .iter()
+        .position(|byte| *byte == b'\n')
+        .map(|position| range.start + position)
+        .unwrap_or(content.len())
+        .max(range.end);
+    content[start..end]
+        .iter()
+        .take(MAX_SNIPPET_LENGTH)
+        .map(|byte| {
+            if byte.is_ascii_graphic() || *byte == b' ' {
+                *byte as char
+            } else {
+                '.'
+            }
+        })
+        .collect::<String>()
+        .trim()
+        .to_string()
+}
+
+impl Module for ModuleHttpFiles {
+    fn name(&self) -> String {
+        String::from("http:files")
+    }
+
+    fn description(&self) -> String {
+        String::from("This module looks for sensitive files exposed on the web servers of a domain")
+    }
+
+    fn subscribers(&self) -> Vec<events::Type> {
+        vec![events::Type::LiveHttpHost(String::new(), String::new())]
+    }
+
+    fn execute(&self, session: &Session, context: Context) -> Result<(), String> {
+        let (domain, base_url) = match context {
+            Context::HttpHost(domain, base_url) => (domain, base_url),
+            _ => {
+                return Err("Received wrong context, exiting module".to_string());
+            }
+        };
+
+        if self.has_processed(base_url.clone()) {
+            return Ok(());
+        }
+        self.process(base_url.clone());
+
+        // Servers answering every path with the same page would match the signatures found in it for every path
+        let catch_all = self
+            .fetch(&format!("{}/{:016x}", base_url, rand::random::<u64>()))
+            .filter(|(status, _)| (200..300).contains(status))
+            .map(|(_, content)| content);
+
+        let concurrency = self.config.concurrency.unwrap_or(10);
+        let mut hits = helpers::parallel::filter_map(
+            self.files.iter().collect(),
+            concurrency,
+            |file: &SensitiveFile| {
+                let url = format!("{}{}", base_url, file.path);
+                let (status, content) = self.fetch(&url)?;
+                if !(200..300).contains(&status) {
+                    return None;
+                }
+                let range = file.find(&content)?;
+                if catch_all
+                    .as_ref()
+                    .is_some_and(|catch_all| file.find(catch_all).is_some())
+                {
+                    return None;
+                }
+                Some((file, url, get_snippet(&content, range)))
+            },
+        );
+        if hits.is_empty() {
+            return Ok(());
+        }
+        hits.sort_by(|(_, a, _), (_, b, _)| a.cmp(b));
+
+        let mut database = session.get_database();
+        let Some(node) = database.search(Type::Domain, domain) else {
+            return Ok(());
+        };
+        for (file, url, snippet) in hits {
+            logger::println(self.name(), format!("'{}' is exposed ({})", url, file.name));
+            node.add_finding(Finding::new(
+                self.name(),
+                file.severity,
+                format!("{} exposed at {}", file.name, url),
+                Some(snippet),
+            ));
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,67 @@
+use std::ops::Range;
+
+use regex::bytes::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::findings::Severity;
+use crate::helpers::entries::Identified;
+
+pub const DEFAULT_PATHS: &str = include_str!("paths.toml");
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct Entry {
+    pub path: String,
+    pub name: String,
+    pub severity: Severity,
+    pub signatures: Vec<String>,
+}
+
+impl Identified for Entry {
+    fn id(&self) -> &str {
+        &self.path
+    }
+}
+
+pub struct SensitiveFile {
+    pub path: String,
+    pub name: String,
+    pub severity: Severity,
+    signatures: Vec<Regex>,
+}
+
+impl SensitiveFile {
+    /// Returns the position of the first signature found in the content
+    pub fn find(&self, content: &[u8]) -> Option<Range<usize>> {
+        self.signatures
+            .iter()
+            .find_map(|signature| signature.find(content))
+            .map(|found| found.range())
+    }
+}
+
+pub fn compile(entries: Vec<Entry>) -> Result<Vec<SensitiveFile>, String> {
+    let mut files = Vec::new();
+    for entry in entries {
+        if !entry.path.starts_with('/') {
+            return Err(format!("'{}' doesn't start with a '/'", entry.path));
+        }
+        // Without a signature, any page served for the path would be a hit
+        if entry.signatures.is_empty() {
+            return Err(format!("'{}' has no signature", entry.path));
+        }
+        files.push(SensitiveFile {
+            signatures: entry
+                .signatures
+                .iter()
+                .map(|signature| {
+                    Regex::new(signature)
+                        .map_err(|e| format!("Invalid signature '{}': {}", signature, e))
+                })
+                .collect::<Result<_, String>>()?,
+            path: entry.path,
+            name: entry.name,
+            severity: entry.severity,
+        });
+    }
+    Ok(files)
+}
@@ -0,0 +1,47 @@
+use crate::helpers::entries;
+use crate::modules::http_files::get_snippet;
+use crate::modules::http_files::paths::{self, Entry};
+
+#[test]
+fn load_embedded_paths() {
+    let files =
+        paths::compile(entries::load(paths::DEFAULT_PATHS, None, "paths").unwrap()).unwrap();
+    assert!(files.iter().any(|file| file.path == "/.git/HEAD"));
+
+    let broken = |signatures: Vec<String>| {
+        paths::compile(vec![Entry {
+            path: String::from("/broken"),
+            name: String::from("Broken"),
+            severity: crate::findings::Severity::Low,
+            signatures,
+        }])
+    };
+    assert!(broken(Vec::new()).is_err());
+    assert!(broken(vec![String::from("(")]).is_err());
+}
+
+#[test]
+fn match_signatures() {
+    let files =
+        paths::compile(entries::load(paths::DEFAULT_PATHS, None, "paths").unwrap()).unwrap();
+    let find = |path: &str, content: &[u8]| {
+        files
+            .iter()
+            .find(|file| file.path == path)
+            .unwrap()
+            .find(content)
+    };
+    assert!(find("/.git/HEAD", b"ref: refs/heads/main\n").is_some());
+    assert!(find("/.git/HEAD", b"<html><body>Not Found</body></html>").is_none());
+    assert!(find("/.DS_Store", b"\x00\x00\x00\x01Bud1\x00\x00\x10\x00").is_some());
+    assert!(find("/backup.tar.gz", b"\x1f\x8b\x08\x00").is_some());
+    assert!(find("/.env", b"APP_ENV=production\nDB_PASSWORD=secret\n").is_some());
+    assert!(find("/.env", b"<!DOCTYPE html>\n<html lang=\"en\">").is_none());
+}
+
+#[test]
+fn extract_snippet() {
+    let content = b"# Settings\nDB_PASSWORD=secret\nOTHER=1";
+    assert_eq!(get_snippet(content, 11..23), "DB_PASSWORD=secret");
+    assert_eq!(get_snippet(b"\x00\x00\x00\x01Bud1\x00", 0..8), "....Bud1.");
+}
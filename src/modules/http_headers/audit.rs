@@ -0,0 +1,357 @@
+use crate::findings::Severity;
+use reqwest::header::{
+    CONTENT_SECURITY_POLICY, CONTENT_SECURITY_POLICY_REPORT_ONLY, HeaderMap, REFERRER_POLICY,
+    SET_COOKIE, STRICT_TRANSPORT_SECURITY, X_CONTENT_TYPE_OPTIONS, X_FRAME_OPTIONS,
+};
+
+/// The minimum max-age of HSTS, in seconds, for it to be eligible for preloading
+const HSTS_PRELOAD_MAX_AGE: u64 = 31536000;
+
+#[derive(Debug, PartialEq)]
+pub struct Issue {
+    pub severity: Severity,
+    pub title: String,
+    /// The value of the header the issue is about
+    pub evidence: Option<String>,
+}
+
+impl Issue {
+    fn new(severity: Severity, title: impl Into<String>, evidence: Option<&str>) -> Self {
+        Issue {
+            severity,
+            title: title.into(),
+            evidence: evidence.map(|evidence| evidence.to_string()),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Hsts {
+    pub max_age: u64,
+    pub include_subdomains: bool,
+    pub preload: bool,
+}
+
+impl Hsts {
+    pub fn parse(value: &str) -> Option<Self> {
+        let mut max_age = None;
+        let mut include_subdomains = false;
+        let mut preload = false;
+        for directive in value.split(';') {
+            let directive = directive.trim().to_lowercase();
+            if let Some(value) = directive.strip_prefix("max-age=") {
+                max_age = value.trim_matches('"').parse().ok();
+            } else if directive == "includesubdomains" {
+                include_subdomains = true;
+            } else if directive == "preload" {
+                preload = true;
+            }
+        }
+        Some(Hsts {
+            max_age: max_age?,
+            include_subdomains,
+            preload,
+        })
+    }
+
+    /// Whether the policy meets the requirements of the HSTS preload list
+    pub fn is_preload_eligible(&self) -> bool {
+        self.max_age >= HSTS_PRELOAD_MAX_AGE && self.include_subdomains && self.preload
+    }
+}
+
+fn get_header(headers: &HeaderMap, name: impl reqwest::header::AsHeaderName) -> Option<&str> {
+    headers
+        .get(name)
+        .and_then(|value| value.to_str().ok())
+        .map(|value| value.trim())
+}
+
+/// Returns the directives of a Content Security Policy, with their lowercased name and sources
+fn parse_csp(value: &str) -> Vec<(String, Vec<String>)> {
+    value
+        .split(';')
+        .filter_map(|directive| {
+            let mut parts = directive.split_whitespace();
+            let name = parts.next()?.to_lowercase();
+            Some((name, parts.map(|source| source.to_lowercase()).collect()))
+        })
+        .collect()
+}
+
+fn audit_hsts(headers: &HeaderMap, issues: &mut Vec<Issue>) -> Option<Hsts> {
+    let Some(value) = get_header(headers, STRICT_TRANSPORT_SECURITY) else {
+        issues.push(Issue::new(
+            Severity::Medium,
+            "Missing Strict-Transport-Security header",
+            None,
+        ));
+        return None;
+    };
+    let Some(hsts) = Hsts::parse(value) else {
+        issues.push(Issue::new(
+            Severity::Medium,
+            "Invalid Strict-Transport-Security header",
+            Some(value),
+        ));
+        return None;
+    };
+    if hsts.max_age == 0 {
+        issues.push(Issue::new(
+            Severity::Medium,
+            "HSTS disabled with a max-age of 0",
+            Some(value),
+        ));
+    } else if hsts.max_age < HSTS_PRELOAD_MAX_AGE {
+        issues.push(Issue::new(
+            Severity::Low,
+            "HSTS max-age shorter than a year",
+            Some(value),
+        ));
+    }
+    if hsts.max_age > 0 && !hsts.is_preload_eligible() {
+        issues.push(Issue::new(
+            Severity::Info,
+            "HSTS not eligible for preloading",
+            Some(value),
+        ));
+    }
+    Some(hsts)
+}
+
+fn audit_csp(headers: &HeaderMap, issues: &mut Vec<Issue>) {
+    let Some(value) = get_header(headers, CONTENT_SECURITY_POLICY) else {
+        if let Some(value) = get_header(headers, CONTENT_SECURITY_POLICY_REPORT_ONLY) {
+            issues.push(Issue::new(
+                Severity::Low,
+                "Content-Security-Policy only reported, not enforced",
+                Some(value),
+            ));
+        } else {
+            issues.push(Issue::new(
+                Severity::Medium,
+                "Missing Content-Security-Policy header",
+                None,
+            ));
+        }
+        return;
+    };
+    let directives = parse_csp(value);
+    let get_sources = |name: &str| {
+        directives
+            .iter()
+            .find(|(directive, _)| directive == name)
+            .map(|(_, sources)| sources)
+    };
+
+    // Scripts fall back to the default sources when they don't have their own
+    let Some(script_sources) = get_sources("script-src").or_else(|| get_sources("default-src"))
+    else {
+        issues.push(Issue::new(
+            Severity::Medium,
+            "Content-Security-Policy doesn't restrict scripts",
+            Some(value),
+        ));
+        return;
+    };
+    let has_source = |expected: &str| script_sources.iter().any(|source| source == expected);
+    // Nonces, hashes and 'strict-dynamic' make browsers ignore 'unsafe-inline'
+    let is_inline_restricted = has_source("'strict-dynamic'")
+        || script_sources.iter().any(|source| {
+            ["'nonce-", "'sha256-", "'sha384-", "'sha512-"]
+                .iter()
+                .any(|prefix| source.starts_with(prefix))
+        });
+    if has_source("'unsafe-inline'") && !is_inline_restricted {
+        issues.push(Issue::new(
+            Severity::Medium,
+            "Content-Security-Policy allows inline scripts",
+            Some(value),
+        ));
+    }
+    if has_source("'unsafe-eval'") {
+        issues.push(Issue::new(
+            Severity::Low,
+            "Content-Security-Policy allows eval",
+            Some(value),
+        ));
+    }
+    if ["*", "http:", "https:", "data:"]
+        .iter()
+        .any(|source| has_source(source))
+    {
+        issues.push(Issue::new(
+            Severity::Medium,
+            "Content-Security-Policy allows scripts from any origin",
+            Some(value),
+        ));
+    }
+    // Plugins can run scripts regardless of script-src
+    let object_sources = get_sources("object-src").or_else(|| get_sources("default-src"));
+    if object_sources.is_none_or(|sources| sources.as_slice() != ["'none'"]) {
+        issues.push(Issue::new(
+            Severity::Low,
+            "Content-Security-Policy allows plugins",
+            Some(value),
+        ));
+    }
+}
+
+fn audit_framing(headers: &HeaderMap, issues: &mut Vec<Issue>) {
+    // frame-ancestors supersedes X-Frame-Options in the browsers supporting it
+    let has_frame_ancestors = get_header(headers, CONTENT_SECURITY_POLICY).is_some_and(|csp| {
+        parse_csp(csp)
+            .iter()
+            .any(|(directive, _)| directive == "frame-ancestors")
+    });
+    if has_frame_ancestors {
+        return;
+    }
+    match get_header(headers, X_FRAME_OPTIONS) {
+        None => issues.push(Issue::new(
+            Severity::Medium,
+            "Missing X-Frame-Options header or frame-ancestors directive",
+            None,
+        )),
+        Some(value) if !["deny", "sameorigin"].contains(&value.to_lowercase().as_str()) => issues
+            .push(Issue::new(
+                Severity::Low,
+                "Invalid X-Frame-Options header",
+                Some(value),
+            )),
+        _ => {}
+    }
+}
+
+fn audit_referrer_policy(headers: &HeaderMap, issues: &mut Vec<Issue>) {
+    let Some(value) = get_header(headers, REFERRER_POLICY) else {
+        issues.push(Issue::new(
+            Severity::Info,
+            "Missing Referrer-Policy header",
+            None,
+        ));
+        return;
+    };
+    // Browsers use the last policy they support
+    let policy = value
+        .split(',')
+        .map(|policy| policy.trim().to_lowercase())
+        .next_back()
+        .unwrap_or_default();
+    if policy == "unsafe-url" || policy == "no-referrer-when-downgrade" {
+        issues.push(Issue::new(
+            Severity::Low,
+            "Referrer-Policy sends the full URL to other origins",
+            Some(value),
+        ));
+    }
+}
+
+fn audit_cookies(headers: &HeaderMap, issues: &mut Vec<Issue>) {
+    for cookie in headers.get_all(SET_COOKIE) {
+        let Ok(cookie) = cookie.to_str() else {
+            continue;
+        };
+        let mut parts = cookie.split(';');
+        let name = parts
+            .next()
+            .and_then(|pair| pair.split_once('='))
+            .map(|(name, _)| name.trim())
+            .unwrap_or_default();
+        let attributes = parts
+            .map(|attribute| attribute.trim().to_lowercase())
+            .collect::<Vec<String>>();
+        let has_attribute = |expected: &str| {
+            attributes
+                .iter()
+                .any(|attribute| attribute.split('=').next().unwrap_or_default().trim() == expected)
+        };
+
+        if !has_attribute("secure") {
+            issues.push(Issue::new(
+                Severity::Medium,
+                format!("Cookie '{}' without the Secure attribute", name),
+                Some(cookie),
+            ));
+        }
+        if !has_attribute("httponly") {
+            issues.push(Issue::new(
+                Severity::Low,
+                format!("Cookie '{}' without the HttpOnly attribute", name),
+                Some(cookie),
+            ));
+        }
+        if !has_attribute("samesite") {
+            issues.push(Issue::new(
+                Severity::Low,
+                format!("Cookie '{}' without the SameSite attribute", name),
+                Some(cookie),
+            ));
+        }
+    }
+}
+
+/// Audits the security headers and cookies of a response, returning the issues found and the parsed HSTS policy
+pub fn audit(headers: &HeaderMap, is_https: bool) -> (Vec<Issue>, Option<Hsts>) {
+    let mut issues = Vec::new();
+    // Browsers ignore HSTS when it's served over HTTP
+    let hsts = if is_https {
+        audit_hsts(headers, &mut issues)
+    } else {
+        None
+    };
+    audit_csp(headers, &mut issues);
+    audit_framing(headers, &mut issues);
+    if get_header(headers, X_CONTENT_TYPE_OPTIONS)
+        .is_none_or(|value| !value.eq_ignore_ascii_case("nosniff"))
+    {
+        issues.push(Issue::new(
+            Severity::Low,
+            "Missing X-Content-Type-Options: nosniff header",
+            get_header(headers, X_CONTENT_TYPE_OPTIONS),
+        ));
+    }
+    audit_referrer_policy(headers, &mut issues);
+    if get_header(headers, "permissions-policy").is_none() {
+        issues.push(Issue::new(
+            Severity::Info,
+            "Missing Permissions-Policy header",
+            None,
+        ));
+    }
+    audit_cookies(headers, &mut issues);
+    (issues, hsts)
+}
+
+/// Audits the cookies set by a redirect to HTTPS, the other headers never being used by browsers
+pub fn audit_redirect(headers: &HeaderMap) -> Vec<Issue> {
+    let mut issues = Vec::new();
+    audit_cookies(headers, &mut issues);
+    issues
+}
+
+/// Scores the issues out of 100, each issue lowering the score according to its severity
+pub fn get_score(issues: &[Issue]) -> u8 {
+    let penalty = issues
+        .iter()
+        .map(|issue| match issue.severity {
+            Severity::Critical => 40,
+            Severity::High => 25,
+            Severity::Medium => 15,
+            Severity::Low => 5,
+            Severity::Info => 0,
+        })
+        .sum::<u32>();
+    100u32.saturating_sub(penalty) as u8
+}
+
+pub fn get_grade(score: u8, issues: &[Issue]) -> &'static str {
+    match score {
+        _ if issues.is_empty() => "A+",
+        90.. => "A",
+        75.. => "B",
+        60.. => "C",
+        45.. => "D",
+        _ => "F",
+    }
+}
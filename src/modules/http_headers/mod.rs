@@ -0,0 +1,199 @@
+use std::sync::Mutex;
+
+use reqwest::Url;
+use reqwest::blocking::Client;
+use reqwest::header::{HeaderMap, LOCATION, USER_AGENT};
+use serde_json::json;
+
+use crate::database::node::{HeadersSummary, Type};
+use crate::findings::Finding;
+use crate::modules::{Context, Module};
+use crate::session::Session;
+use crate::{config, events, helpers, logger};
+
+pub mod audit;
+#[cfg(test)]
+mod tests;
+
+/// The maximum amount of redirects followed on the same host
+const MAX_REDIRECTS: usize = 5;
+
+/// What to do with a response of the web server
+#[derive(Debug, PartialEq)]
+pub enum Step {
+    /// The response is the page to audit
+    Audit,
+    /// The response redirects to another page of the same host
+    Follow(Url),
+    /// The response redirects from HTTP to HTTPS on the same host, browsers never getting to see its headers
+    Upgrade(Url),
+}
+
+pub struct ModuleHttpHeaders {
+    client: Client,
+    processed_urls: Mutex<Vec<String>>,
+}
+
+impl ModuleHttpHeaders {
+    pub fn new(config: config::HttpHeadersConfig) -> Result<Self, String> {
+        let client = helpers::http::client(config.timeout)?;
+        Ok(ModuleHttpHeaders {
+            client,
+            processed_urls: Mutex::new(Vec::new()),
+        })
+    }
+
+    pub fn process(&self, url: String) {
+        self.processed_urls.lock().unwrap().push(url)
+    }
+
+    pub fn has_processed(&self, url: String) -> bool {
+        self.processed_urls.lock().unwrap().contains(&url)
+    }
+}
+
+/// Returns what to do with a response, only redirects staying on the same host being followed
+pub fn get_step(url: &Url, status: u16, headers: &HeaderMap) -> Step {
+    if !(300..400).contains(&status) {
+        return Step::Audit;
+    }
+    let Some(next) = headers
+        .get(LOCATION)
+        .and_then(|location| location.to_str().ok())
+        .and_then(|location| url.join(location).ok())
+    else {
+        return Step::Audit;
+    };
+    if next.host_str() != url.host_str() || &next == url {
+        return Step::Audit;
+    }
+    if url.scheme() == "http" && next.scheme() == "https" {
+        return Step::Upgrade(next);
+    }
+    Step::Follow(next)
+}
+
+impl Module for ModuleHttpHeaders {
+    fn name(&self) -> String {
+        String::from("http:headers")
+    }
+
+    fn description(&self) -> String {
+        String::from(
+            "This module audits the security headers and cookies sent by the web servers of a domain",
+        )
+    }
+
+    fn subscribers(&self) -> Vec<events::Type> {
+        vec![events::Type::LiveHttpHost(String::new(), String::new())]
+    }
+
+    fn execute(&self, session: &Session, context: Context) -> Result<(), String> {
+        let (domain, base_url) = match context {
+            Context::HttpHost(domain, base_url) => (domain, base_url),
+            _ => {
+                return Err("Received wrong context, exiting module".to_string());
+            }
+        };
+
+        if self.has_processed(base_url.clone()) {
+            return Ok(());
+        }
+        self.process(base_url.clone());
+
+        let mut url = Url::parse(&format!("{}/", base_url)).map_err(|e| e.to_string())?;
+        let mut redirects = 0;
+        let mut upgrade = None;
+        let (issues, hsts) = loop {
+            let response = self
+                .client
+                .get(url.clone())
+                .header(USER_AGENT, helpers::ua::get_random())
+                .send()
+                .map_err(|e| format!("Failed to fetch '{}': {}", url, e))?;
+            match get_step(&url, response.status().as_u16(), response.headers()) {
+                Step::Follow(next) if redirects < MAX_REDIRECTS => {
+                    redirects += 1;
+                    url = next;
+                }
+                // The headers that matter are the ones of the HTTPS web server, which is audited on its own
+                Step::Upgrade(next) => {
+                    upgrade = Some(next);
+                    break (audit::audit_redirect(response.headers()), None);
+                }
+                _ => break audit::audit(response.headers(), url.scheme() == "https"),
+            }
+        };
+
+        let mut database = session.get_database();
+        let mut node = database.search(Type::Domain, domain.clone());
+        if let Some(node) = node.as_mut() {
+            for issue in &issues {
+                node.add_finding(Finding::new(
+                    self.name(),
+                    issue.severity,
+                    format!("{} on {}", issue.title, base_url),
+                    issue.evidence.clone(),
+                ));
+            }
+        }
+
+        // Redirects aren't graded, as their headers were never audited
+        if let Some(next) = upgrade {
+            logger::println(
+                self.name(),
+                format!(
+                    "'{}' redirects to '{}' with {} issues",
+                    base_url,
+                    next,
+                    issues.len()
+                ),
+            );
+            if let Some(node) = node {
+                let mut headers = node.get_or_init_map("headers");
+                headers.insert(base_url, json!({ "redirect": next.to_string() }));
+                node.add_data(String::from("headers"), headers.into());
+            }
+            return Ok(());
+        }
+
+        let score = audit::get_score(&issues);
+        let grade = audit::get_grade(score, &issues);
+        logger::println(
+            self.name(),
+            format!(
+                "'{}' is graded {} ({}/100) with {} issues",
+                base_url,
+                grade,
+                score,
+                issues.len()
+            ),
+        );
+        if let Some(node) = node {
+            let mut headers = node.get_or_init_map("headers");
+            headers.insert(
+                base_url.clone(),
+                json!({
+                    "grade": grade,
+                    "score": score,
+                    "hsts": hsts.map(|hsts| json!({
+                        "max_age": hsts.max_age,
+                        "include_subdomains": hsts.include_subdomains,
+                        "preload": hsts.preload,
+                        "preload_eligible": hsts.is_preload_eligible(),
+                    })),
+                }),
+            );
+            node.add_data(String::from("headers"), headers.into());
+        }
+        database.add_headers_summary(HeadersSummary {
+            url: base_url,
+            domain,
+            grade: grade.to_string(),
+            score,
+            issues: issues.len(),
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,175 @@
+use reqwest::Url;
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+
+use crate::findings::Severity;
+use crate::modules::http_headers::audit::{Hsts, audit, audit_redirect, get_grade, get_score};
+use crate::modules::http_headers::{Step, get_step};
+
+fn get_headers(headers: &[(&str, &str)]) -> HeaderMap {
+    let mut map = HeaderMap::new();
+    for (name, value) in headers {
+        map.append(
+            HeaderName::from_bytes(name.as_bytes()).unwrap(),
+            HeaderValue::from_str(value).unwrap(),
+        );
+    }
+    map
+}
+
+#[test]
+fn parse_hsts() {
+    assert_eq!(
+        Hsts::parse("max-age=63072000; includeSubDomains; preload"),
+        Some(Hsts {
+            max_age: 63072000,
+            include_subdomains: true,
+            preload: true,
+        })
+    );
+    assert!(!Hsts::parse("max-age=86400").unwrap().is_preload_eligible());
+    assert_eq!(Hsts::parse("includeSubDomains"), None);
+}
+
+#[test]
+fn grade_hardened_server() {
+    let headers = get_headers(&[
+        (
+            "strict-transport-security",
+            "max-age=63072000; includeSubDomains; preload",
+        ),
+        (
+            "content-security-policy",
+            "default-src 'self'; script-src 'self' 'nonce-abc' 'unsafe-inline'; object-src 'none'; frame-ancestors 'none'",
+        ),
+        ("x-content-type-options", "nosniff"),
+        ("referrer-policy", "strict-origin-when-cross-origin"),
+        ("permissions-policy", "camera=()"),
+        ("set-cookie", "session=1; Secure; HttpOnly; SameSite=Lax"),
+    ]);
+    let (issues, hsts) = audit(&headers, true);
+    assert_eq!(issues, Vec::new());
+    assert!(hsts.unwrap().is_preload_eligible());
+    assert_eq!(get_grade(get_score(&issues), &issues), "A+");
+}
+
+#[test]
+fn grade_weak_server() {
+    let headers = get_headers(&[
+        ("strict-transport-security", "max-age=3600"),
+        (
+            "content-security-policy",
+            "script-src 'self' 'unsafe-inline' https:",
+        ),
+        ("x-frame-options", "ALLOW-FROM https://example.com"),
+        ("referrer-policy", "no-referrer, unsafe-url"),
+        ("set-cookie", "session=1; Path=/"),
+        (
+            "set-cookie",
+            "theme=dark; Secure; HttpOnly; SameSite=Strict",
+        ),
+    ]);
+    let (issues, _) = audit(&headers, true);
+    let titles = issues
+        .iter()
+        .map(|issue| (issue.severity, issue.title.as_str()))
+        .collect::<Vec<(Severity, &str)>>();
+    assert_eq!(
+        titles,
+        vec![
+            (Severity::Low, "HSTS max-age shorter than a year"),
+            (Severity::Info, "HSTS not eligible for preloading"),
+            (
+                Severity::Medium,
+                "Content-Security-Policy allows inline scripts"
+            ),
+            (
+                Severity::Medium,
+                "Content-Security-Policy allows scripts from any origin"
+            ),
+            (Severity::Low, "Content-Security-Policy allows plugins"),
+            (Severity::Low, "Invalid X-Frame-Options header"),
+            (
+                Severity::Low,
+                "Missing X-Content-Type-Options: nosniff header"
+            ),
+            (
+                Severity::Low,
+                "Referrer-Policy sends the full URL to other origins"
+            ),
+            (Severity::Info, "Missing Permissions-Policy header"),
+            (
+                Severity::Medium,
+                "Cookie 'session' without the Secure attribute"
+            ),
+            (
+                Severity::Low,
+                "Cookie 'session' without the HttpOnly attribute"
+            ),
+            (
+                Severity::Low,
+                "Cookie 'session' without the SameSite attribute"
+            ),
+        ]
+    );
+    assert_eq!(get_score(&issues), 20);
+    assert_eq!(get_grade(get_score(&issues), &issues), "F");
+}
+
+#[test]
+fn skip_hsts_over_http() {
+    let (issues, hsts) = audit(&get_headers(&[("set-cookie", "id=1")]), false);
+    assert_eq!(hsts, None);
+    assert!(
+        issues
+            .iter()
+            .all(|issue| !issue.title.contains("Strict-Transport-Security"))
+    );
+    assert_eq!(
+        issues
+            .iter()
+            .filter(|issue| issue.title.starts_with("Cookie"))
+            .count(),
+        3
+    );
+}
+
+#[test]
+fn audit_redirect_cookies() {
+    let headers = get_headers(&[
+        ("location", "https://example.com/"),
+        ("set-cookie", "session=1; HttpOnly; SameSite=Lax"),
+    ]);
+    let issues = audit_redirect(&headers);
+    assert_eq!(
+        issues
+            .iter()
+            .map(|issue| (issue.severity, issue.title.as_str()))
+            .collect::<Vec<(Severity, &str)>>(),
+        vec![(
+            Severity::Medium,
+            "Cookie 'session' without the Secure attribute"
+        )]
+    );
+    assert!(audit_redirect(&get_headers(&[("location", "https://example.com/")])).is_empty());
+}
+
+#[test]
+fn follow_redirects() {
+    let url = Url::parse("http://example.com/").unwrap();
+    let redirect = |location: &str| get_headers(&[("location", location)]);
+    assert_eq!(
+        get_step(&url, 301, &redirect("https://example.com/")),
+        Step::Upgrade(Url::parse("https://example.com/").unwrap())
+    );
+    assert_eq!(
+        get_step(&url, 302, &redirect("/home")),
+        Step::Follow(Url::parse("http://example.com/home").unwrap())
+    );
+    // Other hosts are audited on their own
+    assert_eq!(
+        get_step(&url, 301, &redirect("https://www.example.com/")),
+        Step::Audit
+    );
+    assert_eq!(get_step(&url, 200, &redirect("/home")), Step::Audit);
+    assert_eq!(get_step(&url, 302, &HeaderMap::new()), Step::Audit);
+}
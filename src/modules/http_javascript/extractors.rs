@@ -0,0 +1,240 @@
+use std::sync::LazyLock;
+
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use regex::Regex;
+use reqwest::Url;
+use serde::Serialize;
+
+use crate::helpers;
+use crate::modules::http_javascript::secrets::{self, Secret};
+
+static SCRIPT: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?is)<script\b([^>]*)>(.*?)</script\s*>").unwrap());
+
+static SRC: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"(?i)\bsrc\s*=\s*(?:"([^"]*)"|'([^']*)'|([^\s>]+))"#).unwrap());
+
+static SOURCE_MAPPING_URL: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?m)^[ \t]*//[#@][ \t]*sourceMappingURL=(\S+)[ \t]*$").unwrap());
+
+static ENDPOINT: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"["'`](/[A-Za-z0-9_-][^"'`\s<>\\]*)["'`]|(https?://[A-Za-z0-9.-]+(?::[0-9]+)?(?:/[^"'`\s<>\\)]*)?)"#).unwrap()
+});
+
+static HOSTNAME: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)\b(?:[a-z0-9](?:[a-z0-9-]{0,61}[a-z0-9])?\.)+[a-z][a-z0-9-]{0,62}").unwrap()
+});
+
+static BUCKETS: LazyLock<Vec<(&str, Regex)>> = LazyLock::new(|| {
+    [
+        (
+            "s3",
+            r"\b([a-z0-9][a-z0-9.-]{1,61}[a-z0-9])\.s3(?:[.-][a-z0-9-]+)*\.amazonaws\.com",
+        ),
+        (
+            "s3",
+            r"//s3(?:[.-][a-z0-9-]+)*\.amazonaws\.com/([a-z0-9][a-z0-9.-]{1,61}[a-z0-9])",
+        ),
+        ("s3", r"\bs3://([a-z0-9][a-z0-9.-]{1,61}[a-z0-9])"),
+        (
+            "gcs",
+            r"\b([a-z0-9][a-z0-9._-]{1,61}[a-z0-9])\.storage\.googleapis\.com",
+        ),
+        (
+            "gcs",
+            r"//storage\.googleapis\.com/([a-z0-9][a-z0-9._-]{1,61}[a-z0-9])",
+        ),
+        ("gcs", r"\bgs://([a-z0-9][a-z0-9._-]{1,61}[a-z0-9])"),
+        ("azure", r"\b([a-z0-9]{3,24})\.blob\.core\.windows\.net"),
+        (
+            "spaces",
+            r"\b([a-z0-9][a-z0-9-]{1,61}[a-z0-9])\.[a-z0-9]+\.digitaloceanspaces\.com",
+        ),
+    ]
+    .into_iter()
+    .map(|(provider, pattern)| (provider, Regex::new(pattern).unwrap()))
+    .collect()
+});
+
+/// The extensions of the paths that are assets rather than endpoints
+const STATIC_EXTENSIONS: [&str; 16] = [
+    ".css", ".png", ".jpg", ".jpeg", ".gif", ".svg", ".ico", ".webp", ".woff", ".woff2", ".ttf",
+    ".eot", ".otf", ".mp4", ".webm", ".map",
+];
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Kind {
+    Endpoint,
+    Hostname,
+    Bucket,
+    Secret,
+}
+
+#[derive(Debug)]
+pub struct Extracted<'a> {
+    pub kind: Kind,
+    pub value: String,
+    /// The line of the content the value was found on, starting at 1
+    pub line: usize,
+    /// The secret pattern that matched, for the secrets
+    pub secret: Option<&'a Secret>,
+}
+
+/// Returns the URLs of the external scripts of a page, and the content of its inline scripts with the line they start on
+pub fn get_scripts(html: &str, page_url: &Url) -> (Vec<Url>, Vec<(String, usize)>) {
+    let newlines = get_newlines(html);
+    let mut external = Vec::new();
+    let mut inline = Vec::new();
+    for captures in SCRIPT.captures_iter(html) {
+        let attributes = &captures[1];
+        if let Some(src) = SRC.captures(attributes).and_then(|src| {
+            src.get(1)
+                .or_else(|| src.get(2))
+                .or_else(|| src.get(3))
+                .map(|src| src.as_str().trim().to_string())
+        }) {
+            if let Ok(url) = page_url.join(&src)
+                && !external.contains(&url)
+            {
+                external.push(url);
+            }
+        } else if let Some(content) = captures.get(2)
+            && !content.as_str().trim().is_empty()
+        {
+            let line = get_line(&newlines, content.start());
+            inline.push((content.as_str().to_string(), line));
+        }
+    }
+    (external, inline)
+}
+
+/// Returns the value of the last `sourceMappingURL` comment of a script
+pub fn get_source_mapping_url(content: &str) -> Option<String> {
+    SOURCE_MAPPING_URL
+        .captures_iter(content)
+        .last()
+        .map(|captures| captures[1].to_string())
+}
+
+/// Decodes a source map embedded in a `data:` URL
+pub fn decode_data_url(url: &str) -> Option<String> {
+    let (metadata, data) = url.strip_prefix("data:")?.split_once(',')?;
+    if metadata.ends_with(";base64") {
+        String::from_utf8(STANDARD.decode(data).ok()?).ok()
+    } else {
+        Some(data.to_string())
+    }
+}
+
+/// Returns the name and the content of the original sources embedded in a source map
+pub fn get_sources(source_map: &str) -> Vec<(String, String)> {
+    let Ok(source_map) = serde_json::from_str::<serde_json::Value>(source_map) else {
+        return Vec::new();
+    };
+    let (Some(sources), Some(contents)) = (
+        source_map["sources"].as_array(),
+        source_map["sourcesContent"].as_array(),
+    ) else {
+        return Vec::new();
+    };
+    sources
+        .iter()
+        .zip(contents)
+        .filter_map(|(source, content)| {
+            Some((source.as_str()?.to_string(), content.as_str()?.to_string()))
+        })
+        .collect()
+}
+
+fn get_newlines(content: &str) -> Vec<usize> {
+    content
+        .match_indices('\n')
+        .map(|(offset, _)| offset)
+        .collect()
+}
+
+/// Returns the line of the offset, starting at 1, from the offsets of the newlines of the content
+fn get_line(newlines: &[usize], offset: usize) -> usize {
+    newlines.partition_point(|newline| *newline < offset) + 1
+}
+
+/// Extracts the endpoints, hostnames of the target, cloud buckets and secrets found in a script
+pub fn extract<'a>(content: &str, target: &str, secrets: &'a [Secret]) -> Vec<Extracted<'a>> {
+    let newlines = get_newlines(content);
+    let mut extracted = Vec::new();
+    let mut push = |kind: Kind, value: String, offset: usize, secret: Option<&'a Secret>| {
+        if !extracted
+            .iter()
+            .any(|existing: &Extracted| existing.kind == kind && existing.value == value)
+        {
+            extracted.push(Extracted {
+                kind,
+                value,
+                line: get_line(&newlines, offset),
+                secret,
+            });
+        }
+    };
+
+    let is_in_scope = |host: &str| host == target || host.ends_with(&format!(".{}", target));
+    for captures in ENDPOINT.captures_iter(content) {
+        let Some(endpoint) = captures.get(1).or_else(|| captures.get(2)) else {
+            continue;
+        };
+        let value = endpoint.as_str();
+        let path = value.split(['?', '#']).next().unwrap_or_default();
+        if STATIC_EXTENSIONS
+            .iter()
+            .any(|extension| path.to_lowercase().ends_with(extension))
+        {
+            continue;
+        }
+        // Only the absolute URLs of the target are endpoints of it
+        if value.starts_with("http")
+            && !Url::parse(value)
+                .ok()
+                .and_then(|url| url.host_str().map(|host| is_in_scope(&host.to_lowercase())))
+                .unwrap_or(false)
+        {
+            continue;
+        }
+        push(Kind::Endpoint, value.to_string(), endpoint.start(), None);
+    }
+
+    for found in HOSTNAME.find_iter(content) {
+        let hostname = found.as_str().to_lowercase();
+        if hostname != target
+            && is_in_scope(&hostname)
+            && !helpers::dns::continues_hostname(&content[found.end()..])
+        {
+            push(Kind::Hostname, hostname, found.start(), None);
+        }
+    }
+
+    for (provider, pattern) in BUCKETS.iter() {
+        for captures in pattern.captures_iter(content) {
+            let bucket = captures.get(1).unwrap();
+            push(
+                Kind::Bucket,
+                format!("{}:{}", provider, bucket.as_str()),
+                bucket.start(),
+                None,
+            );
+        }
+    }
+
+    for secret in secrets {
+        for found in secret.pattern.find_iter(content) {
+            push(
+                Kind::Secret,
+                secrets::mask(found.as_str()),
+                found.start(),
+                Some(secret),
+            );
+        }
+    }
+
+    extracted
+}
@@ -0,0 +1,360 @@
+use std::io::Read;
+use std::sync::Mutex;
+
+use reqwest::blocking::Client;
+use reqwest::header::USER_AGENT;
+use reqwest::{Url, redirect};
+use serde::Serialize;
+
+use crate::database::node::{Node, Type};
+use crate::findings::{Finding, Severity};
+use crate::modules::http_javascript::extractors::{Extracted, Kind};
+use crate::modules::http_javascript::secrets::Secret;
+use crate::modules::{Context, Module};
+use crate::session::Session;
+use crate::{config, events, helpers, logger};
+
+mod extractors;
+mod secrets;
+#[cfg(test)]
+mod tests;
+
+/// The maximum amount of redirects followed on the same host when fetching a page
+const MAX_REDIRECTS: usize = 5;
+
+/// A value extracted from a script, with where it was found
+#[derive(Debug, Serialize)]
+struct Record<'a> {
+    #[serde(rename = "type")]
+    kind: Kind,
+    value: String,
+    file: String,
+    line: usize,
+    #[serde(skip)]
+    secret: Option<&'a Secret>,
+}
+
+impl<'a> Record<'a> {
+    fn new(extracted: Extracted<'a>, file: &str, first_line: usize) -> Self {
+        Record {
+            kind: extracted.kind,
+            value: extracted.value,
+            file: file.to_string(),
+            line: extracted.line + first_line - 1,
+            secret: extracted.secret,
+        }
+    }
+}
+
+struct Script<'a> {
+    records: Vec<Record<'a>>,
+    /// The URL of the source map that could be downloaded, and the amount of original sources it has
+    source_map: Option<(String, usize)>,
+}
+
+pub struct ModuleHttpJavascript {
+    config: config::HttpJavascriptConfig,
+    client: Client,
+    secrets: Vec<Secret>,
+    processed_urls: Mutex<Vec<String>>,
+    processed_scripts: Mutex<Vec<Url>>,
+}
+
+impl ModuleHttpJavascript {
+    pub fn new(config: config::HttpJavascriptConfig) -> Result<Self, String> {
+        let client = helpers::http::client_with_policy(
+            config.timeout,
+            redirect::Policy::custom(|attempt| {
+                let same_host = attempt.previous().first().and_then(|url| url.host_str())
+                    == attempt.url().host_str();
+                if same_host && attempt.previous().len() <= MAX_REDIRECTS {
+                    attempt.follow()
+                } else {
+                    attempt.stop()
+                }
+            }),
+        )?;
+        let secrets = secrets::compile(helpers::entries::load(
+            secrets::DEFAULT_SECRETS,
+            config.secrets.as_deref(),
+            "secrets",
+        )?)?;
+        Ok(ModuleHttpJavascript {
+            config,
+            client,
+            secrets,
+            processed_urls: Mutex::new(Vec::new()),
+            processed_scripts: Mutex::new(Vec::new()),
+        })
+    }
+
+    pub fn process(&self, url: String) {
+        self.processed_urls.lock().unwrap().push(url)
+    }
+
+    pub fn has_processed(&self, url: String) -> bool {
+        self.processed_urls.lock().unwrap().contains(&url)
+    }
+
+    /// Keeps up to the given amount of scripts that haven't been analyzed yet, as the same script is often served on several hosts, and marks them as analyzed
+    fn process_scripts(&self, scripts: Vec<Url>, max_scripts: usize) -> Vec<Url> {
+        let mut processed_scripts = self.processed_scripts.lock().unwrap();
+        let scripts = scripts
+            .into_iter()
+            .filter(|script| !processed_scripts.contains(script))
+            .take(max_scripts)
+            .collect::<Vec<Url>>();
+        processed_scripts.extend(scripts.iter().cloned());
+        scripts
+    }
+
+    /// Fetches the size-capped content served at the URL, with the URL it was served at after the redirects and its source map header
+    fn fetch(&self, url: Url) -> Option<(Url, String, Option<String>)> {
+        let response = self
+            .client
+            .get(url)
+            .header(USER_AGENT, helpers::ua::get_random())
+            .send()
+            .ok()?;
+        if !response.status().is_success() {
+            return None;
+        }
+        let final_url = response.url().clone();
+        let source_map = ["SourceMap", "X-SourceMap"].iter().find_map(|header| {
+            response
+                .headers()
+                .get(*header)
+                .and_then(|value| value.to_str().ok())
+                .map(|value| value.to_string())
+        });
+        let mut content = Vec::new();
+        let max_size = self.config.max_size.unwrap_or(5120) * 1024;
+        let _ = response.take(max_size).read_to_end(&mut content);
+        Some((
+            final_url,
+            String::from_utf8_lossy(&content).to_string(),
+            source_map,
+        ))
+    }
+
+    fn analyze_script(&self, session: &Session, url: Url) -> Option<Script<'_>> {
+        let (url, content, source_map_header) = self.fetch(url)?;
+        let target = &session.get_args().domain;
+        let mut script = Script {
+            records: extractors::extract(&content, target, &self.secrets)
+                .into_iter()
+                .map(|extracted| Record::new(extracted, url.as_str(), 1))
+                .collect(),
+            source_map: None,
+        };
+
+        let Some(source_map_url) =
+            source_map_header.or_else(|| extractors::get_source_mapping_url(&content))
+        else {
+            return Some(script);
+        };
+        let (source_map_url, source_map) = if source_map_url.starts_with("data:") {
+            (None, extractors::decode_data_url(&source_map_url))
+        } else {
+            match url
+                .join(&source_map_url)
+                .ok()
+                .and_then(|source_map_url| self.fetch(source_map_url))
+            {
+                Some((source_map_url, source_map, _)) => {
+                    (Some(source_map_url.to_string()), Some(source_map))
+                }
+                None => (None, None),
+            }
+        };
+        let sources = extractors::get_sources(&source_map.unwrap_or_default());
+        // Pages served for any path aren't source maps, so only the ones with sources are reported
+        if let Some(source_map_url) = source_map_url
+            && !sources.is_empty()
+        {
+            script.source_map = Some((source_map_url, sources.len()));
+        }
+        for (source, content) in sources {
+            script.records.extend(
+                extractors::extract(&content, target, &self.secrets)
+                    .into_iter()
+                    .map(|extracted| Record::new(extracted, &source, 1)),
+            );
+        }
+        Some(script)
+    }
+}
+
+impl Module for ModuleHttpJavascript {
+    fn name(&self) -> String {
+        String::from("http:javascript")
+    }
+
+    fn description(&self) -> String {
+        String::from(
+            "This module will extract endpoints, hostnames, cloud buckets and secrets from the scripts of the web servers of a domain",
+        )
+    }
+
+    fn subscribers(&self) -> Vec<events::Type> {
+        vec![events::Type::LiveHttpHost(String::new(), String::new())]
+    }
+
+    fn execute(&self, session: &Session, context: Context) -> Result<(), String> {
+        let (domain, base_url) = match context {
+            Context::HttpHost(domain, base_url) => (domain, base_url),
+            _ => {
+                return Err("Received wrong context, exiting module".to_string());
+            }
+        };
+
+        if self.has_processed(base_url.clone()) {
+            return Ok(());
+        }
+        self.process(base_url.clone());
+
+        let Ok(url) = Url::parse(&base_url) else {
+            return Ok(());
+        };
+        let Some((page_url, html, _)) = self.fetch(url) else {
+            return Ok(());
+        };
+        let (scripts, inline_scripts) = extractors::get_scripts(&html, &page_url);
+
+        let target = &session.get_args().domain;
+        let mut records = Vec::new();
+        for (content, first_line) in inline_scripts {
+            records.extend(
+                extractors::extract(&content, target, &self.secrets)
+                    .into_iter()
+                    .map(|extracted| Record::new(extracted, page_url.as_str(), first_line)),
+            );
+        }
+
+        // Third-party scripts, such as the ones of analytics services, aren't about the target
+        let third_party = self.config.third_party.unwrap_or(false);
+        let scripts = self.process_scripts(
+            scripts
+                .into_iter()
+                .filter(|script| {
+                    third_party
+                        || script
+                            .host_str()
+                            .is_some_and(|host| session.is_in_scope(host))
+                })
+                .collect(),
+            self.config.max_scripts.unwrap_or(50),
+        );
+        let script_count = scripts.len();
+        let concurrency = self.config.concurrency.unwrap_or(5);
+        let mut source_maps = Vec::new();
+        for script in helpers::parallel::filter_map(scripts, concurrency, |script| {
+            self.analyze_script(session, script)
+        }) {
+            records.extend(script.records);
+            source_maps.extend(script.source_map);
+        }
+
+        let mut unique_records: Vec<Record> = Vec::new();
+        for record in records {
+            if !unique_records
+                .iter()
+                .any(|existing| existing.kind == record.kind && existing.value == record.value)
+            {
+                unique_records.push(record);
+            }
+        }
+        let records = unique_records;
+        if records.is_empty() && source_maps.is_empty() {
+            return Ok(());
+        }
+        let count = |kind: Kind| records.iter().filter(|record| record.kind == kind).count();
+        logger::println(
+            self.name(),
+            format!(
+                "Extracted {} endpoints, {} hostnames, {} buckets and {} secrets from {} scripts of '{}'",
+                count(Kind::Endpoint),
+                count(Kind::Hostname),
+                count(Kind::Bucket),
+                count(Kind::Secret),
+                script_count,
+                base_url
+            ),
+        );
+
+        for record in &records {
+            if record.kind == Kind::Hostname
+                && record.value != domain
+                && &record.value != target
+                && session.is_in_scope(&record.value)
+            {
+                session.discover_domain(&self.name(), record.value.clone(), None);
+            }
+        }
+
+        let mut database = session.get_database();
+        let prefix = format!("{}/", base_url);
+        for record in &records {
+            if record.kind != Kind::Endpoint {
+                continue;
+            }
+            let url = if record.value.starts_with('/') {
+                format!("{}{}", base_url, record.value)
+            } else {
+                record.value.clone()
+            };
+            if url.starts_with(&prefix)
+                && database.search(Type::Url, url.clone()).is_none()
+                && let Some(node) = database.search(Type::Domain, domain.clone())
+            {
+                node.connect(Node::new(Type::Url, url));
+            }
+        }
+
+        let Some(node) = database.search(Type::Domain, domain) else {
+            return Ok(());
+        };
+        for (source_map, sources) in source_maps {
+            logger::println(
+                self.name(),
+                format!("'{}' is exposed ({} sources)", source_map, sources),
+            );
+            node.add_finding(Finding::new(
+                self.name(),
+                Severity::Low,
+                format!("Source map exposed at {}", source_map),
+                Some(format!("{} original sources", sources)),
+            ));
+        }
+        for record in &records {
+            if let Some(secret) = record.secret {
+                logger::println(
+                    self.name(),
+                    format!(
+                        "'{}' contains a secret ({}) on line {}",
+                        record.file, secret.name, record.line
+                    ),
+                );
+                node.add_finding(Finding::new(
+                    self.name(),
+                    secret.severity,
+                    format!("{} leaked in {}", secret.name, record.file),
+                    Some(format!("Line {}: {}", record.line, record.value)),
+                ));
+            }
+        }
+        let mut javascript = node
+            .get_data("javascript")
+            .and_then(|javascript| javascript.as_array())
+            .cloned()
+            .unwrap_or_default();
+        javascript.extend(
+            records
+                .iter()
+                .filter_map(|record| serde_json::to_value(record).ok()),
+        );
+        node.add_data(String::from("javascript"), javascript.into());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,55 @@
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::findings::Severity;
+use crate::helpers::entries::Identified;
+
+pub const DEFAULT_SECRETS: &str = include_str!("secrets.toml");
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct Entry {
+    pub name: String,
+    pub severity: Severity,
+    pub pattern: String,
+}
+
+impl Identified for Entry {
+    fn id(&self) -> &str {
+        &self.name
+    }
+}
+
+#[derive(Debug)]
+pub struct Secret {
+    pub name: String,
+    pub severity: Severity,
+    pub pattern: Regex,
+}
+
+pub fn compile(entries: Vec<Entry>) -> Result<Vec<Secret>, String> {
+    entries
+        .into_iter()
+        .map(|entry| {
+            Ok(Secret {
+                pattern: Regex::new(&entry.pattern)
+                    .map_err(|e| format!("Invalid pattern '{}': {}", entry.pattern, e))?,
+                name: entry.name,
+                severity: entry.severity,
+            })
+        })
+        .collect()
+}
+
+/// Masks a secret before it's written to the reports, only keeping its first and last 4 characters
+pub fn mask(secret: &str) -> String {
+    let chars = secret.chars().collect::<Vec<char>>();
+    if chars.len() <= 12 {
+        return "*".repeat(chars.len());
+    }
+    format!(
+        "{}{}{}",
+        chars[..4].iter().collect::<String>(),
+        "*".repeat(chars.len() - 8),
+        chars[chars.len() - 4..].iter().collect::<String>()
+    )
+}
@@ -0,0 +1,251 @@
+use std::io::Read;
+use std::sync::{LazyLock, Mutex};
+
+use regex::Regex;
+use reqwest::Url;
+use reqwest::blocking::{Client, Response};
+use reqwest::header::{LOCATION, USER_AGENT};
+use serde::Serialize;
+
+use crate::database::node::Type;
+use crate::modules::http_probe::technologies::{DetectedTechnology, Technologies};
+use crate::modules::{Context, Module};
+use crate::session::Session;
+use crate::{config, events, helpers, logger};
+
+mod technologies;
+#[cfg(test)]
+mod tests;
+
+/// The maximum amount of bytes read from a response body
+const MAX_BODY_SIZE: u64 = 1024 * 1024;
+
+static TITLE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?is)<title[^>]*>(.*?)</title>").unwrap());
+
+#[derive(Debug, Default, Serialize)]
+struct Probe {
+    available: bool,
+    status: Option<u16>,
+    title: Option<String>,
+    server: Option<String>,
+    powered_by: Option<String>,
+    content_length: Option<u64>,
+    final_url: Option<String>,
+    redirects: Vec<String>,
+    technologies: Vec<DetectedTechnology>,
+}
+
+pub struct ModuleHttpProbe {
+    config: config::HttpProbeConfig,
+    client: Client,
+    technologies: Technologies,
+    processed_urls: Mutex<Vec<String>>,
+}
+
+impl ModuleHttpProbe {
+    pub fn new(config: config::HttpProbeConfig) -> Result<Self, String> {
+        let client = helpers::http::client(config.timeout)?;
+        let technologies = Technologies::load(config.signatures.as_deref())?;
+        Ok(ModuleHttpProbe {
+            config,
+            client,
+            technologies,
+            processed_urls: Mutex::new(Vec::new()),
+        })
+    }
+
+    pub fn process(&self, url: String) {
+        self.processed_urls.lock().unwrap().push(url)
+    }
+
+    pub fn has_processed(&self, url: String) -> bool {
+        self.processed_urls.lock().unwrap().contains(&url)
+    }
+
+    fn send(&self, url: &Url) -> Option<Response> {
+        self.client
+            .get(url.clone())
+            .header(USER_AGENT, helpers::ua::get_random())
+            .send()
+            .ok()
+    }
+
+    fn probe(&self, base_url: &str) -> Probe {
+        let max_redirects = self.config.max_redirects.unwrap_or(10);
+        let Ok(mut url) = Url::parse(base_url) else {
+            return Probe::default();
+        };
+        let Some(mut response) = self.send(&url) else {
+            return Probe::default();
+        };
+
+        // Follow the redirects manually to keep track of the chain
+        let mut redirects = Vec::new();
+        while response.status().is_redirection() && redirects.len() < max_redirects {
+            let Some(location) = response
+                .headers()
+                .get(LOCATION)
+                .and_then(|location| location.to_str().ok())
+                .and_then(|location| url.join(location).ok())
+            else {
+                break;
+            };
+            let Some(next_response) = self.send(&location) else {
+                break;
+            };
+            redirects.push(location.to_string());
+            url = location;
+            response = next_response;
+        }
+
+        let headers = response
+            .headers()
+            .iter()
+            .map(|(name, value)| {
+                (
+                    name.as_str().to_lowercase(),
+                    String::from_utf8_lossy(value.as_bytes()).to_string(),
+                )
+            })
+            .collect::<Vec<(String, String)>>();
+        let get_header = |name: &str| {
+            headers
+                .iter()
+                .find(|(header, _)| header == name)
+                .map(|(_, value)| value.clone())
+        };
+        let status = response.status().as_u16();
+        let mut body = Vec::new();
+        let _ = response.take(MAX_BODY_SIZE).read_to_end(&mut body);
+        let body = String::from_utf8_lossy(&body);
+
+        Probe {
+            available: true,
+            status: Some(status),
+            title: extract_title(&body),
+            server: get_header("server"),
+            powered_by: get_header("x-powered-by"),
+            content_length: get_header("content-length")
+                .and_then(|length| length.parse().ok())
+                .or(Some(body.len() as u64)),
+            final_url: Some(url.to_string()),
+            redirects,
+            technologies: self.technologies.detect(&headers, &body),
+        }
+    }
+}
+
+/// Extracts the title of an HTML page, with its whitespaces collapsed
+pub fn extract_title(body: &str) -> Option<String> {
+    let title = TITLE
+        .captures(body)?
+        .get(1)?
+        .as_str()
+        .split_whitespace()
+        .collect::<Vec<&str>>()
+        .join(" ");
+    if title.is_empty() {
+        None
+    } else {
+        Some(title.chars().take(256).collect())
+    }
+}
+
+/// Whether the first redirect of a web server goes to HTTPS on the same host, in which case it only serves as an upgrade to the HTTPS web server
+pub fn is_https_upgrade(base_url: &str, redirects: &[String]) -> bool {
+    let (Ok(url), Some(Ok(location))) = (
+        Url::parse(base_url),
+        redirects.first().map(|location| Url::parse(location)),
+    ) else {
+        return false;
+    };
+    url.scheme() == "http" && location.scheme() == "https" && url.host_str() == location.host_str()
+}
+
+impl Module for ModuleHttpProbe {
+    fn name(&self) -> String {
+        String::from("http:probe")
+    }
+
+    fn description(&self) -> String {
+        String::from(
+            "This module probes the web servers of a domain and fingerprints the technologies they use",
+        )
+    }
+
+    fn subscribers(&self) -> Vec<events::Type> {
+        vec![
+            events::Type::DiscoveredDomain(String::new()),
+            events::Type::OpenPort(String::new(), 0),
+        ]
+    }
+
+    fn execute(&self, session: &Session, context: Context) -> Result<(), String> {
+        let is_port = matches!(context, Context::Port(_, _));
+        let (domain, base_urls) = match context {
+            Context::Domain(domain) => (
+                domain.clone(),
+                vec![format!("https://{}", domain), format!("http://{}", domain)],
+            ),
+            Context::Port(domain, port) => {
+                if port == 80 || port == 443 {
+                    return Ok(());
+                }
+                (
+                    domain.clone(),
+                    vec![
+                        format!("https://{}:{}", domain, port),
+                        format!("http://{}:{}", domain, port),
+                    ],
+                )
+            }
+            _ => {
+                return Err("Received wrong context, exiting module".to_string());
+            }
+        };
+
+        for base_url in base_urls {
+            if self.has_processed(base_url.clone()) {
+                continue;
+            }
+            self.process(base_url.clone());
+
+            let probe = self.probe(&base_url);
+            if !probe.available && is_port {
+                // Only keep track of the scheme availability of the default ports
+                continue;
+            }
+            if probe.available {
+                logger::println(
+                    self.name(),
+                    format!(
+                        "'{}' is live with status {}{}",
+                        base_url,
+                        probe.status.unwrap_or_default(),
+                        probe
+                            .title
+                            .as_ref()
+                            .map(|title| format!(" $[fg:gray]({})", title))
+                            .unwrap_or_default()
+                    ),
+                );
+            }
+
+            if let Some(node) = session.get_database().search(Type::Domain, domain.clone()) {
+                let mut http = node.get_or_init_map("http");
+                http.insert(
+                    base_url.clone(),
+                    serde_json::to_value(&probe).map_err(|e| e.to_string())?,
+                );
+                node.add_data(String::from("http"), http.into());
+            }
+            // The modules auditing web servers would otherwise go through the HTTPS one twice
+            if probe.available && !is_https_upgrade(&base_url, &probe.redirects) {
+                session.emit(events::Type::LiveHttpHost(domain.clone(), base_url));
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,76 @@
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::helpers::signatures::{self, HeaderMatcher, HeaderPatterns};
+
+const DEFAULT_SIGNATURES: &str = include_str!("technologies.json");
+
+#[derive(Debug, Deserialize)]
+struct Signature {
+    name: String,
+    #[serde(flatten)]
+    patterns: HeaderPatterns,
+    /// Patterns matching the body
+    #[serde(default)]
+    body: Vec<String>,
+}
+
+struct Technology {
+    name: String,
+    matcher: HeaderMatcher,
+    body: Vec<Regex>,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+pub struct DetectedTechnology {
+    pub name: String,
+    pub version: Option<String>,
+}
+
+pub struct Technologies {
+    technologies: Vec<Technology>,
+}
+
+impl Technologies {
+    /// Loads the signatures from the given file, or the embedded ones if there's none
+    pub fn load(path: Option<&str>) -> Result<Self, String> {
+        Self::compile(signatures::load(DEFAULT_SIGNATURES, path)?)
+    }
+
+    fn compile(signatures: Vec<Signature>) -> Result<Self, String> {
+        let mut technologies = Vec::new();
+        for signature in signatures {
+            technologies.push(Technology {
+                matcher: HeaderMatcher::new(&signature.patterns)?,
+                body: signatures::compile_all(&signature.body)?,
+                name: signature.name,
+            });
+        }
+        Ok(Technologies { technologies })
+    }
+
+    /// Detects the technologies from the headers, with lowercase names, and the body of a response
+    pub fn detect(&self, headers: &[(String, String)], body: &str) -> Vec<DetectedTechnology> {
+        let mut detected = Vec::new();
+        for technology in &self.technologies {
+            let mut captures = technology.matcher.captures(headers);
+            captures.extend(
+                technology
+                    .body
+                    .iter()
+                    .filter_map(|pattern| pattern.captures(body)),
+            );
+            if captures.is_empty() {
+                continue;
+            }
+            detected.push(DetectedTechnology {
+                name: technology.name.clone(),
+                version: captures
+                    .iter()
+                    .find_map(|captures| captures.get(1))
+                    .map(|version| version.as_str().to_string()),
+            });
+        }
+        detected
+    }
+}
@@ -0,0 +1,82 @@
+use crate::modules::http_probe::technologies::{DetectedTechnology, Technologies};
+use crate::modules::http_probe::{extract_title, is_https_upgrade};
+
+fn headers(headers: &[(&str, &str)]) -> Vec<(String, String)> {
+    headers
+        .iter()
+        .map(|(name, value)| (name.to_string(), value.to_string()))
+        .collect()
+}
+
+#[test]
+fn extract_page_title() {
+    assert_eq!(
+        extract_title("<html><head><title>\n  Example   Domain\n</title></head></html>"),
+        Some(String::from("Example Domain"))
+    );
+    assert_eq!(
+        extract_title("<TITLE lang=\"en\">Hi</TITLE>"),
+        Some(String::from("Hi"))
+    );
+    assert_eq!(extract_title("<title></title>"), None);
+    assert_eq!(extract_title("no title"), None);
+}
+
+#[test]
+fn load_embedded_signatures() {
+    assert!(Technologies::load(None).is_ok());
+}
+
+#[test]
+fn detect_technologies() {
+    let technologies = Technologies::load(None).unwrap();
+    let detected = technologies.detect(
+        &headers(&[
+            ("server", "nginx/1.18.0"),
+            ("x-powered-by", "PHP/8.1.2"),
+            ("set-cookie", "PHPSESSID=abc; path=/"),
+        ]),
+        "<link rel='stylesheet' href='/wp-content/themes/x/style.css'>",
+    );
+    assert!(detected.contains(&DetectedTechnology {
+        name: String::from("nginx"),
+        version: Some(String::from("1.18.0")),
+    }));
+    assert!(detected.contains(&DetectedTechnology {
+        name: String::from("PHP"),
+        version: Some(String::from("8.1.2")),
+    }));
+    assert!(detected.iter().any(|t| t.name == "WordPress"));
+    assert!(!detected.iter().any(|t| t.name == "Drupal"));
+}
+
+#[test]
+fn detect_header_presence() {
+    let technologies = Technologies::load(None).unwrap();
+    let detected = technologies.detect(&headers(&[("cf-ray", "8a1b2c3d4e5f-AMS")]), "");
+    assert_eq!(
+        detected,
+        vec![DetectedTechnology {
+            name: String::from("Cloudflare"),
+            version: None,
+        }]
+    );
+}
+
+#[test]
+fn skip_https_upgrades() {
+    let redirects = |location: &str| vec![location.to_string()];
+    assert!(is_https_upgrade(
+        "http://example.com",
+        &redirects("https://example.com/")
+    ));
+    assert!(!is_https_upgrade(
+        "http://example.com",
+        &redirects("https://www.example.com/")
+    ));
+    assert!(!is_https_upgrade(
+        "http://example.com",
+        &redirects("http://example.com/home")
+    ));
+    assert!(!is_https_upgrade("http://example.com", &[]));
+}
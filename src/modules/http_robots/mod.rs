@@ -0,0 +1,222 @@
+use std::collections::VecDeque;
+use std::io::Read;
+use std::sync::Mutex;
+
+use reqwest::Url;
+use reqwest::blocking::Client;
+use reqwest::header::USER_AGENT;
+
+use crate::database::node::{Node, Type};
+use crate::modules::{Context, Module};
+use crate::session::Session;
+use crate::{config, events, helpers, logger};
+
+mod parsers;
+#[cfg(test)]
+mod tests;
+
+/// The maximum amount of bytes read from a file, once decompressed
+const MAX_CONTENT_SIZE: u64 = 10 * 1024 * 1024;
+
+pub struct ModuleHttpRobots {
+    config: config::HttpRobotsConfig,
+    client: Client,
+    processed_urls: Mutex<Vec<String>>,
+}
+
+impl ModuleHttpRobots {
+    pub fn new(config: config::HttpRobotsConfig) -> Result<Self, String> {
+        let client = helpers::http::client(config.timeout)?;
+        Ok(ModuleHttpRobots {
+            config,
+            client,
+            processed_urls: Mutex::new(Vec::new()),
+        })
+    }
+
+    pub fn process(&self, url: String) {
+        self.processed_urls.lock().unwrap().push(url)
+    }
+
+    pub fn has_processed(&self, url: String) -> bool {
+        self.processed_urls.lock().unwrap().contains(&url)
+    }
+
+    /// Fetches the content served at the URL, decompressing it when it is gzipped
+    fn fetch(&self, url: &str) -> Option<String> {
+        let response = self
+            .client
+            .get(url)
+            .header(USER_AGENT, helpers::ua::get_random())
+            .send()
+            .ok()?;
+        if !response.status().is_success() {
+            return None;
+        }
+        let mut content = Vec::new();
+        response
+            .take(MAX_CONTENT_SIZE)
+            .read_to_end(&mut content)
+            .ok()?;
+        let content = parsers::decode(content, MAX_CONTENT_SIZE);
+        Some(String::from_utf8_lossy(&content).to_string())
+    }
+
+    /// Fetches the sitemaps and the ones nested in sitemap indexes, returning the URLs of the pages they list
+    fn crawl_sitemaps(&self, session: &Session, sitemaps: Vec<String>) -> Vec<String> {
+        let max_sitemaps = self.config.max_sitemaps.unwrap_or(20);
+        let mut queue = VecDeque::from(sitemaps);
+        let mut visited = Vec::new();
+        let mut urls = Vec::new();
+        while let Some(sitemap) = queue.pop_front() {
+            if visited.len() >= max_sitemaps {
+                break;
+            }
+            // Sitemaps can be hosted anywhere, only the ones of the target are fetched
+            if visited.contains(&sitemap)
+                || !get_host(&sitemap).is_some_and(|host| session.is_in_scope(&host))
+            {
+                continue;
+            }
+            visited.push(sitemap.clone());
+            let Some(content) = self.fetch(&sitemap) else {
+                continue;
+            };
+            let parsed = parsers::parse_sitemap(&content);
+            if session.get_state().is_debug_or_verbose() {
+                logger::info(
+                    self.name(),
+                    format!(
+                        "Parsed '{}' with {} URLs and {} nested sitemaps",
+                        sitemap,
+                        parsed.urls.len(),
+                        parsed.sitemaps.len()
+                    ),
+                );
+            }
+            queue.extend(parsed.sitemaps);
+            urls.extend(parsed.urls);
+        }
+        urls
+    }
+}
+
+/// Returns the lowercased hostname of the URL
+pub fn get_host(url: &str) -> Option<String> {
+    Url::parse(url)
+        .ok()?
+        .host_str()
+        .map(|host| host.trim_end_matches('.').to_lowercase())
+}
+
+impl Module for ModuleHttpRobots {
+    fn name(&self) -> String {
+        String::from("http:robots")
+    }
+
+    fn description(&self) -> String {
+        String::from(
+            "This module will harvest the paths, hostnames and emails of the robots.txt, sitemaps and security.txt of the web servers of a domain",
+        )
+    }
+
+    fn subscribers(&self) -> Vec<events::Type> {
+        vec![events::Type::LiveHttpHost(String::new(), String::new())]
+    }
+
+    fn execute(&self, session: &Session, context: Context) -> Result<(), String> {
+        let (domain, base_url) = match context {
+            Context::HttpHost(domain, base_url) => (domain, base_url),
+            _ => {
+                return Err("Received wrong context, exiting module".to_string());
+            }
+        };
+
+        if self.has_processed(base_url.clone()) {
+            return Ok(());
+        }
+        self.process(base_url.clone());
+
+        let robots = self
+            .fetch(&format!("{}/robots.txt", base_url))
+            .map(|content| parsers::parse_robots(&content))
+            .unwrap_or_default();
+        let sitemaps = if robots.sitemaps.is_empty() {
+            vec![format!("{}/sitemap.xml", base_url)]
+        } else {
+            robots.sitemaps.clone()
+        };
+        let security_txt = self
+            .fetch(&format!("{}/.well-known/security.txt", base_url))
+            .or_else(|| self.fetch(&format!("{}/security.txt", base_url)))
+            .map(|content| parsers::parse_security_txt(&content))
+            .unwrap_or_default();
+
+        let mut urls = robots
+            .paths
+            .iter()
+            .map(|path| format!("{}{}", base_url, path))
+            .collect::<Vec<String>>();
+        urls.extend(self.crawl_sitemaps(session, sitemaps));
+        urls.extend(robots.sitemaps);
+        urls.extend(security_txt.urls);
+
+        let mut hosts = urls
+            .iter()
+            .filter_map(|url| get_host(url))
+            .filter(|host| {
+                host != &domain && host != &session.get_args().domain && session.is_in_scope(host)
+            })
+            .collect::<Vec<String>>();
+        hosts.sort();
+        hosts.dedup();
+        for host in hosts {
+            session.discover_domain(&self.name(), host, None);
+        }
+
+        let prefix = format!("{}/", base_url);
+        let max_urls = self.config.max_urls.unwrap_or(500);
+        let mut paths = urls
+            .into_iter()
+            .filter(|url| url.starts_with(&prefix))
+            .collect::<Vec<String>>();
+        paths.sort();
+        paths.dedup();
+        if paths.len() > max_urls {
+            logger::warn(
+                self.name(),
+                format!(
+                    "Found {} URLs on '{}', only keeping the first {}",
+                    paths.len(),
+                    base_url,
+                    max_urls
+                ),
+            );
+            paths.truncate(max_urls);
+        }
+
+        let mut database = session.get_database();
+        let mut added = 0;
+        for url in paths {
+            if database.search(Type::Url, url.clone()).is_none()
+                && let Some(node) = database.search(Type::Domain, domain.clone())
+            {
+                node.connect(Node::new(Type::Url, url));
+                added += 1;
+            }
+        }
+        if added > 0 {
+            logger::println(
+                self.name(),
+                format!("Found {} URLs on '{}'", added, base_url),
+            );
+        }
+
+        drop(database);
+        for email in security_txt.emails {
+            session.discover_email(&self.name(), email, &domain);
+        }
+
+        Ok(())
+    }
+}
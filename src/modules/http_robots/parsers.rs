@@ -0,0 +1,145 @@
+use std::io::Read;
+use std::sync::LazyLock;
+
+use flate2::read::GzDecoder;
+use regex::Regex;
+
+static LOC: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?is)<loc>\s*(?:<!\[CDATA\[)?\s*(.*?)\s*(?:\]\]>)?\s*</loc>").unwrap()
+});
+
+static EMAIL: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^[a-zA-Z0-9._%+-]+@[a-zA-Z0-9.-]+\.[a-zA-Z]{2,}$").unwrap());
+
+#[derive(Debug, Default, PartialEq)]
+pub struct Robots {
+    /// The paths of the `Allow` and `Disallow` rules, without their wildcards
+    pub paths: Vec<String>,
+    /// The URLs of the `Sitemap` entries
+    pub sitemaps: Vec<String>,
+}
+
+#[derive(Debug, Default, PartialEq)]
+pub struct Sitemap {
+    /// The URLs of the nested sitemaps, when it is a sitemap index
+    pub sitemaps: Vec<String>,
+    /// The URLs of the pages
+    pub urls: Vec<String>,
+}
+
+#[derive(Debug, Default, PartialEq)]
+pub struct SecurityTxt {
+    /// The emails of the `Contact` fields
+    pub emails: Vec<String>,
+    /// The web URLs of every field
+    pub urls: Vec<String>,
+}
+
+/// Returns the lowercased name and the value of every field of a `Name: value` file, without the comments
+fn get_fields(content: &str) -> Vec<(String, String)> {
+    content
+        .lines()
+        .filter_map(|line| {
+            let line = line.split('#').next().unwrap_or_default();
+            let (name, value) = line.split_once(':')?;
+            let value = value.trim();
+            (!value.is_empty()).then(|| (name.trim().to_lowercase(), value.to_string()))
+        })
+        .collect()
+}
+
+fn push_unique(values: &mut Vec<String>, value: String) {
+    if !values.contains(&value) {
+        values.push(value);
+    }
+}
+
+pub fn parse_robots(content: &str) -> Robots {
+    let mut robots = Robots::default();
+    for (name, value) in get_fields(content) {
+        match name.as_str() {
+            "allow" | "disallow" => {
+                // The path is only known up to its first wildcard
+                let path = value
+                    .split(['*', '$'])
+                    .next()
+                    .unwrap_or_default()
+                    .to_string();
+                if path.starts_with('/') && path != "/" {
+                    push_unique(&mut robots.paths, path);
+                }
+            }
+            "sitemap" => push_unique(&mut robots.sitemaps, value),
+            _ => {}
+        }
+    }
+    robots
+}
+
+/// Parses an XML sitemap or sitemap index, or a text sitemap listing one URL per line
+pub fn parse_sitemap(content: &str) -> Sitemap {
+    let mut sitemap = Sitemap::default();
+    let is_index = content.contains("<sitemapindex");
+    let locations = LOC
+        .captures_iter(content)
+        .map(|captures| captures[1].to_string())
+        .collect::<Vec<String>>();
+    let locations = if locations.is_empty() && !content.trim_start().starts_with('<') {
+        content
+            .lines()
+            .map(|line| line.trim().to_string())
+            .filter(|line| line.starts_with("http://") || line.starts_with("https://"))
+            .collect()
+    } else {
+        locations
+    };
+    for location in locations {
+        let location = unescape(&location);
+        if is_index {
+            push_unique(&mut sitemap.sitemaps, location);
+        } else {
+            push_unique(&mut sitemap.urls, location);
+        }
+    }
+    sitemap
+}
+
+pub fn parse_security_txt(content: &str) -> SecurityTxt {
+    let mut security_txt = SecurityTxt::default();
+    for (name, value) in get_fields(content) {
+        if value.starts_with("http://") || value.starts_with("https://") {
+            push_unique(&mut security_txt.urls, value);
+        } else if name == "contact" {
+            let email = value.trim_start_matches("mailto:").to_lowercase();
+            if EMAIL.is_match(&email) {
+                push_unique(&mut security_txt.emails, email);
+            }
+        }
+    }
+    security_txt
+}
+
+/// Replaces the entities that have to be escaped in the URLs of a sitemap
+fn unescape(value: &str) -> String {
+    value
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+/// Decompresses the content when it is gzipped, reading at most the given amount of bytes
+pub fn decode(content: Vec<u8>, limit: u64) -> Vec<u8> {
+    if !content.starts_with(&[0x1f, 0x8b]) {
+        return content;
+    }
+    let mut decoded = Vec::new();
+    match GzDecoder::new(content.as_slice())
+        .take(limit)
+        .read_to_end(&mut decoded)
+    {
+        Ok(_) => decoded,
+        Err(_) => Vec::new(),
+    }
+}
@@ -0,0 +1,81 @@
+use std::io::Write;
+
+use flate2::Compression;
+use flate2::write::GzEncoder;
+
+use crate::modules::http_robots::parsers::{
+    decode, parse_robots, parse_security_txt, parse_sitemap,
+};
+
+#[test]
+fn parse_robots_txt() {
+    let robots = parse_robots(
+        "User-agent: *\nDisallow: /admin/ # Back office\nDisallow: /\nallow: /api/*/public\nDisallow: /*.bak$\nDisallow:\nDisallow: /admin/\n\nSitemap: https://example.com/sitemap_index.xml\n",
+    );
+    assert_eq!(robots.paths, vec!["/admin/", "/api/"]);
+    assert_eq!(
+        robots.sitemaps,
+        vec!["https://example.com/sitemap_index.xml"]
+    );
+}
+
+#[test]
+fn parse_sitemaps() {
+    let index = parse_sitemap(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<sitemapindex xmlns="http://www.sitemaps.org/schemas/sitemap/0.9">
+  <sitemap><loc>https://example.com/posts.xml.gz</loc></sitemap>
+  <sitemap><loc><![CDATA[https://example.com/pages.xml]]></loc></sitemap>
+</sitemapindex>"#,
+    );
+    assert_eq!(
+        index.sitemaps,
+        vec![
+            "https://example.com/posts.xml.gz",
+            "https://example.com/pages.xml"
+        ]
+    );
+    assert!(index.urls.is_empty());
+
+    let urlset = parse_sitemap(
+        r#"<urlset xmlns="http://www.sitemaps.org/schemas/sitemap/0.9">
+  <url><loc>
+    https://staging.example.com/search?q=a&amp;page=2
+  </loc><lastmod>2024-01-01</lastmod></url>
+</urlset>"#,
+    );
+    assert_eq!(
+        urlset.urls,
+        vec!["https://staging.example.com/search?q=a&page=2"]
+    );
+
+    let text = parse_sitemap("https://example.com/a\nhttps://example.com/b\n");
+    assert_eq!(
+        text.urls,
+        vec!["https://example.com/a", "https://example.com/b"]
+    );
+}
+
+#[test]
+fn parse_security_txt_contacts() {
+    let security_txt = parse_security_txt(
+        "# Our security policy\nContact: mailto:Security@Example.com\nContact: https://bugs.example.com/report\nContact: tel:+1-201-555-0123\nExpires: 2030-01-01T00:00:00.000Z\nPolicy: https://example.com/policy\n",
+    );
+    assert_eq!(security_txt.emails, vec!["security@example.com"]);
+    assert_eq!(
+        security_txt.urls,
+        vec![
+            "https://bugs.example.com/report",
+            "https://example.com/policy"
+        ]
+    );
+}
+
+#[test]
+fn decode_gzipped_content() {
+    let content = b"<urlset><url><loc>https://example.com/</loc></url></urlset>";
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(content).unwrap();
+    assert_eq!(decode(encoder.finish().unwrap(), 1024), content);
+    assert_eq!(decode(content.to_vec(), 1024), content);
+}
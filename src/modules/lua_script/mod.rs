@@ -67,6 +67,9 @@ impl Module for ModuleLuaScript {
                     "Ready" => Some(events::Type::Ready),
                     "DiscoveredDomain" => Some(events::Type::DiscoveredDomain(String::new())),
                     "OpenPort" => Some(events::Type::OpenPort(String::new(), 0)),
+                    "LiveHttpHost" => {
+                        Some(events::Type::LiveHttpHost(String::new(), String::new()))
+                    }
                     _ => None,
                 })
                 .collect()
@@ -1,20 +1,47 @@
 use crate::events;
 use crate::session::Session;
 
+pub mod cloud_detect;
+pub mod dns_axfr;
+pub mod dns_bruteforce;
+pub mod dns_permutations;
+pub mod dns_records;
+pub mod dns_reverse;
 pub mod domain_takeover;
+pub mod domain_whois;
 pub mod dork;
+pub mod email_security;
+pub mod http_bruteforce;
+pub mod http_crawler;
+pub mod http_files;
+pub mod http_headers;
+pub mod http_javascript;
+pub mod http_probe;
+pub mod http_robots;
 pub mod lua_script;
 pub mod passive_dns;
+pub mod port_scan;
 pub mod ready;
+pub mod service_detection;
+pub mod tls_inspect;
+pub mod tls_scan;
 
 pub enum Context {
     Domain(String),
+    /// String: the domain
+    /// usize: the open port
+    Port(String, usize),
+    /// String: the domain
+    /// String: the base URL of the web server
+    HttpHost(String, String),
     None,
 }
 
 pub fn get_context_for_event(event: &events::Type) -> Context {
     match event {
         events::Type::DiscoveredDomain(domain) => Context::Domain(domain.clone()),
+        events::Type::OpenPort(domain, port) => Context::Port(domain.clone(), *port),
+        events::Type::LiveHttpHost(domain, url) => Context::HttpHost(domain.clone(), url.clone()),
         _ => Context::None,
     }
 }
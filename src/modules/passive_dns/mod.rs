@@ -1,14 +1,12 @@
 use chrono::{Duration, Utc};
-use serde_json::Value;
 use std::sync::Mutex;
 
 use reqwest::header::USER_AGENT;
 
-use crate::database::node::{Node, Type};
 use crate::modules::passive_dns::crt_sh::CrtShItem;
 use crate::modules::{Context, Module};
 use crate::session::Session;
-use crate::{config, events, flags, helpers, logger};
+use crate::{config, events, flags, helpers};
 
 mod crt_sh;
 
@@ -106,42 +104,16 @@ impl Module for ModulePassiveDNS {
                                 flags |= flags::domain::IS_RECENT;
                             }
 
-                            logger::println(
-                                self.name(),
-                                format!(
-                                    "Discovered '{}' as a new subdomain{}{}",
-                                    name_value,
-                                    if has_expired {
-                                        " $[fg:red]$[effect:bold](Certificate expired, likely inactive)"
-                                    } else {
-                                        ""
-                                    },
-                                    if is_recent {
-                                        " $[fg:blue]$[effect:bold](Active since less than 24 hours)"
-                                    } else {
-                                        ""
-                                    }
-                                ),
+                            let Some(mut new_node) = session.new_domain_node(name_value) else {
+                                continue;
+                            };
+                            new_node.add_flag(flags);
+                            session.add_domain_node(
+                                &self.name(),
+                                name_value.clone(),
+                                new_node,
+                                Some(&domain),
                             );
-
-                            if let Some(parent) =
-                                session.get_database().search(Type::Domain, domain.clone())
-                            {
-                                let mut new_node = Node::new(Type::Domain, name_value.clone());
-                                new_node.add_flag(flags);
-                                if let Some(ip_addr) = helpers::network::get_ip_addr(name_value) {
-                                    new_node.add_data(
-                                        String::from("ip"),
-                                        Value::String(ip_addr.to_string()),
-                                    );
-                                    if let Some(geoinfo) = helpers::network::geolocate_ip(ip_addr) {
-                                        new_node.add_data(String::from("geoinfo"), geoinfo.into())
-                                    }
-                                }
-                                parent.connect(new_node);
-                            }
-                            session.get_state().discover_domain(name_value.to_string());
-                            session.emit(events::Type::DiscoveredDomain(name_value.clone()));
                         }
                     }
                 }
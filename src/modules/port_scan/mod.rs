@@ -0,0 +1,115 @@
+use std::collections::HashMap;
+use std::net::{IpAddr, SocketAddr, TcpStream};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use serde_json::Value;
+
+use crate::database::node::Type;
+use crate::modules::{Context, Module};
+use crate::session::Session;
+use crate::{config, events, helpers, logger};
+
+mod ports;
+#[cfg(test)]
+mod tests;
+
+pub struct ModulePortScan {
+    config: config::PortScanConfig,
+    ports: Vec<u16>,
+    processed_domains: Mutex<Vec<String>>,
+    scanned_ips: Mutex<HashMap<IpAddr, Vec<u16>>>,
+}
+
+impl ModulePortScan {
+    pub fn new(config: config::PortScanConfig) -> Result<Self, String> {
+        let ports = ports::parse(config.ports.as_deref().unwrap_or("top-100"))?;
+        Ok(ModulePortScan {
+            config,
+            ports,
+            processed_domains: Mutex::new(Vec::new()),
+            scanned_ips: Mutex::new(HashMap::new()),
+        })
+    }
+
+    pub fn process(&self, domain: String) {
+        self.processed_domains.lock().unwrap().push(domain)
+    }
+
+    pub fn has_processed(&self, domain: String) -> bool {
+        self.processed_domains.lock().unwrap().contains(&domain)
+    }
+
+    fn scan(&self, ip_addr: IpAddr) -> Vec<u16> {
+        if let Some(open_ports) = self.scanned_ips.lock().unwrap().get(&ip_addr) {
+            return open_ports.clone();
+        }
+
+        let timeout = Duration::from_millis(self.config.timeout.unwrap_or(1000));
+        let concurrency = self.config.concurrency.unwrap_or(100);
+        let mut open_ports =
+            helpers::parallel::filter_map(self.ports.clone(), concurrency, |port| {
+                TcpStream::connect_timeout(&SocketAddr::new(ip_addr, port), timeout)
+                    .ok()
+                    .map(|_| port)
+            });
+        open_ports.sort();
+        self.scanned_ips
+            .lock()
+            .unwrap()
+            .insert(ip_addr, open_ports.clone());
+        open_ports
+    }
+}
+
+impl Module for ModulePortScan {
+    fn name(&self) -> String {
+        String::from("port:scan")
+    }
+
+    fn description(&self) -> String {
+        String::from("This module will find the open TCP ports of a domain by connecting to them")
+    }
+
+    fn subscribers(&self) -> Vec<events::Type> {
+        vec![events::Type::DiscoveredDomain(String::new())]
+    }
+
+    fn execute(&self, session: &Session, context: Context) -> Result<(), String> {
+        let domain = match context {
+            Context::Domain(domain) => domain,
+            _ => {
+                return Err("Received wrong context, exiting module".to_string());
+            }
+        };
+
+        if self.has_processed(domain.to_string()) {
+            return Ok(());
+        }
+        self.process(domain.to_string());
+
+        let Some(ip_addr) = session.get_resolver().lookup_ip(&domain).first().copied() else {
+            return Ok(());
+        };
+        let open_ports = self.scan(ip_addr);
+        if open_ports.is_empty() {
+            return Ok(());
+        }
+
+        if let Some(node) = session.get_database().search(Type::Domain, domain.clone()) {
+            node.add_data(
+                String::from("open_ports"),
+                Value::Array(open_ports.iter().map(|port| (*port).into()).collect()),
+            );
+        }
+        for port in open_ports {
+            logger::println(
+                self.name(),
+                format!("Discovered port {} as open on '{}'", port, domain),
+            );
+            session.emit(events::Type::OpenPort(domain.clone(), port as usize));
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,39 @@
+use std::collections::BTreeSet;
+
+/// The 100 most common TCP ports, as ranked by Nmap
+pub const TOP_100: [u16; 100] = [
+    7, 9, 13, 21, 22, 23, 25, 26, 37, 53, 79, 80, 81, 88, 106, 110, 111, 113, 119, 135, 139, 143,
+    144, 179, 199, 389, 427, 443, 444, 445, 465, 513, 514, 515, 543, 544, 548, 554, 587, 631, 646,
+    873, 990, 993, 995, 1025, 1026, 1027, 1028, 1029, 1110, 1433, 1720, 1723, 1755, 1900, 2000,
+    2001, 2049, 2121, 2717, 3000, 3128, 3306, 3389, 3986, 4899, 5000, 5009, 5051, 5060, 5101, 5190,
+    5357, 5432, 5631, 5666, 5800, 5900, 6000, 6001, 6646, 7070, 8000, 8008, 8009, 8080, 8081, 8443,
+    8888, 9100, 9999, 10000, 32768, 49152, 49153, 49154, 49155, 49156, 49157,
+];
+
+/// Parses a port specification such as `top-100`, `1-1024` or `22,80,443,8000-8100`
+pub fn parse(spec: &str) -> Result<Vec<u16>, String> {
+    let mut ports = BTreeSet::new();
+    for item in spec.split(',').map(|item| item.trim()) {
+        let range = if item.eq_ignore_ascii_case("top-100") {
+            TOP_100.to_vec()
+        } else if let Some((start, end)) = item.split_once('-') {
+            let start = parse_port(start)?;
+            let end = parse_port(end)?;
+            if start > end {
+                return Err(format!("Invalid port range '{}'", item));
+            }
+            (start..=end).collect()
+        } else {
+            vec![parse_port(item)?]
+        };
+        ports.extend(range);
+    }
+    Ok(ports.into_iter().collect())
+}
+
+fn parse_port(port: &str) -> Result<u16, String> {
+    match port.trim().parse::<u16>() {
+        Ok(0) | Err(_) => Err(format!("Invalid port '{}'", port.trim())),
+        Ok(port) => Ok(port),
+    }
+}
@@ -0,0 +1,32 @@
+use crate::modules::port_scan::ports;
+
+#[test]
+fn parse_single_ports() {
+    assert_eq!(ports::parse("22,80,443").unwrap(), vec![22, 80, 443]);
+    assert_eq!(ports::parse("443, 22").unwrap(), vec![22, 443]);
+}
+
+#[test]
+fn parse_port_ranges() {
+    assert_eq!(ports::parse("1-1024").unwrap().len(), 1024);
+    assert_eq!(
+        ports::parse("8000-8002,22").unwrap(),
+        vec![22, 8000, 8001, 8002]
+    );
+}
+
+#[test]
+fn parse_top_ports() {
+    let parsed = ports::parse("top-100").unwrap();
+    assert_eq!(parsed.len(), 100);
+    assert!(parsed.contains(&443));
+    assert_eq!(ports::parse("top-100,80,6379").unwrap().len(), 101);
+}
+
+#[test]
+fn parse_invalid_ports() {
+    assert!(ports::parse("0").is_err());
+    assert!(ports::parse("65536").is_err());
+    assert!(ports::parse("1024-1").is_err());
+    assert!(ports::parse("http").is_err());
+}
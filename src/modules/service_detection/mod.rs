@@ -0,0 +1,162 @@
+use std::io::{Read, Write};
+use std::net::{SocketAddr, TcpStream};
+use std::time::Duration;
+
+use serde_json::Value;
+
+use crate::database::node::{Node, Type};
+use crate::modules::service_detection::signatures::Service;
+use crate::modules::{Context, Module};
+use crate::session::Session;
+use crate::{config, events, logger};
+
+mod signatures;
+#[cfg(test)]
+mod tests;
+
+/// The maximum amount of bytes read from a service
+const MAX_RESPONSE_SIZE: usize = 4096;
+
+pub struct ModuleServiceDetection {
+    config: config::ServiceDetectionConfig,
+}
+
+impl ModuleServiceDetection {
+    pub fn new(config: config::ServiceDetectionConfig) -> Self {
+        ModuleServiceDetection { config }
+    }
+
+    /// Connects to the service, optionally sends a probe, and returns what the service has sent back
+    fn exchange(&self, address: SocketAddr, probe: Option<&[u8]>) -> Vec<u8> {
+        let timeout = Duration::from_millis(self.config.timeout.unwrap_or(3000));
+        let Ok(mut stream) = TcpStream::connect_timeout(&address, timeout) else {
+            return Vec::new();
+        };
+        if let Some(probe) = probe
+            && stream.write_all(probe).is_err()
+        {
+            return Vec::new();
+        }
+
+        let mut response = Vec::new();
+        let mut buffer = [0; 1024];
+        let _ = stream.set_read_timeout(Some(timeout));
+        while response.len() < MAX_RESPONSE_SIZE {
+            match stream.read(&mut buffer) {
+                Ok(0) | Err(_) => break,
+                Ok(read) => {
+                    response.extend_from_slice(&buffer[..read]);
+                    // The rest of the response, if any, is expected to follow quickly
+                    let _ = stream.set_read_timeout(Some(Duration::from_millis(300)));
+                }
+            }
+        }
+        response.truncate(MAX_RESPONSE_SIZE);
+        response
+    }
+
+    fn detect(&self, domain: &str, address: SocketAddr) -> (Option<Service>, Option<String>) {
+        // Many services greet the client first, the others need to be probed
+        let probes: [Option<Vec<u8>>; 3] = [
+            None,
+            Some(format!("HEAD / HTTP/1.0\r\nHost: {}\r\n\r\n", domain).into_bytes()),
+            Some(b"PING\r\nINFO server\r\n".to_vec()),
+        ];
+        let mut first_banner = None;
+        for probe in probes {
+            let response = self.exchange(address, probe.as_deref());
+            if response.is_empty() {
+                continue;
+            }
+            let banner = String::from_utf8_lossy(&response)
+                .lines()
+                .next()
+                .unwrap_or_default()
+                .chars()
+                .filter(|c| c.is_ascii_graphic() || *c == ' ')
+                .take(256)
+                .collect::<String>()
+                .trim()
+                .to_string();
+            if let Some(service) = signatures::identify(&response) {
+                return (Some(service), (!banner.is_empty()).then_some(banner));
+            }
+            if first_banner.is_none() && !banner.is_empty() {
+                first_banner = Some(banner);
+            }
+        }
+        (None, first_banner)
+    }
+}
+
+impl Module for ModuleServiceDetection {
+    fn name(&self) -> String {
+        String::from("service:detection")
+    }
+
+    fn description(&self) -> String {
+        String::from(
+            "This module identifies the service behind an open port by reading its banner or probing it",
+        )
+    }
+
+    fn subscribers(&self) -> Vec<events::Type> {
+        vec![events::Type::OpenPort(String::new(), 0)]
+    }
+
+    fn execute(&self, session: &Session, context: Context) -> Result<(), String> {
+        let (domain, port) = match context {
+            Context::Port(domain, port) => (domain, port),
+            _ => {
+                return Err("Received wrong context, exiting module".to_string());
+            }
+        };
+
+        let Some(ip_addr) = session.get_resolver().lookup_ip(&domain).first().copied() else {
+            return Err(format!("Failed resolving '{}'", domain));
+        };
+        let (service, banner) = self.detect(&domain, SocketAddr::new(ip_addr, port as u16));
+
+        let mut port_node = Node::new(Type::Port, format!("{}:{}", domain, port));
+        port_node.add_data(String::from("port"), port.into());
+        match &service {
+            Some(service) => {
+                logger::println(
+                    self.name(),
+                    format!(
+                        "Identified '{}' on port {} of '{}'{}",
+                        service.name,
+                        port,
+                        domain,
+                        match (&service.product, &service.version) {
+                            (Some(product), Some(version)) => format!(" ({} {})", product, version),
+                            (Some(product), None) => format!(" ({})", product),
+                            _ => String::new(),
+                        }
+                    ),
+                );
+                port_node.add_data(String::from("service"), service.name.clone().into());
+                if let Some(product) = &service.product {
+                    port_node.add_data(String::from("product"), product.clone().into());
+                }
+                if let Some(version) = &service.version {
+                    port_node.add_data(String::from("version"), version.clone().into());
+                }
+            }
+            None => {
+                if let Some(name) = signatures::guess_from_port(port) {
+                    port_node.add_data(String::from("service"), name.into());
+                }
+            }
+        }
+        if let Some(banner) = banner {
+            port_node.add_data(String::from("banner"), Value::String(banner));
+        }
+
+        if let Some(parent) = session.get_database().search(Type::Domain, domain.clone()) {
+            parent.connect(port_node);
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,144 @@
+use std::sync::LazyLock;
+
+use regex::Regex;
+
+#[derive(Debug, Default, PartialEq)]
+pub struct Service {
+    pub name: String,
+    pub product: Option<String>,
+    pub version: Option<String>,
+}
+
+/// Signatures matched against the first line of a response, the `product` and `version` groups are optional
+static SIGNATURES: LazyLock<Vec<(&str, Regex)>> = LazyLock::new(|| {
+    [
+        (
+            "ssh",
+            r"^SSH-[\d.]+-(?P<product>[A-Za-z]+)(?:[_-](?P<version>[\w.]+))?",
+        ),
+        (
+            "ftp",
+            r"^220[ -].*?(?P<product>vsFTPd|ProFTPD|Pure-FTPd|FileZilla Server)(?: version)? ?(?P<version>\d[\w.]*)?",
+        ),
+        ("ftp", r"^220[ -].*\bFTP\b"),
+        (
+            "smtp",
+            r"^220[ -].*?(?P<product>Postfix|Exim|Sendmail|OpenSMTPD|Microsoft ESMTP MAIL Service)(?:[ /](?P<version>\d[\w.]*))?",
+        ),
+        ("smtp", r"^220[ -].*\bE?SMTP\b"),
+        ("pop3", r"^\+OK(?:.*?(?P<product>Dovecot|Cyrus|Courier))?"),
+        ("imap", r"^\* OK(?:.*?(?P<product>Dovecot|Cyrus|Courier))?"),
+        ("http", r"^HTTP/\d(?:\.\d)? \d{3}"),
+        ("redis", r"^(?:\+PONG|-NOAUTH|-DENIED)"),
+    ]
+    .into_iter()
+    .map(|(name, signature)| (name, Regex::new(signature).unwrap()))
+    .collect()
+});
+
+static HTTP_SERVER: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?im)^Server: *(?P<product>[^/\r\n ]+)(?:/(?P<version>[^ \r\n]+))?").unwrap()
+});
+
+static REDIS_VERSION: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"redis_version:(?P<version>[\w.]+)").unwrap());
+
+/// Identifies the service from the raw bytes it has sent
+pub fn identify(response: &[u8]) -> Option<Service> {
+    if let Some(service) = identify_mysql(response) {
+        return Some(service);
+    }
+
+    let text = String::from_utf8_lossy(response);
+    let first_line = text.lines().next().unwrap_or_default();
+    let (name, captures) = SIGNATURES
+        .iter()
+        .find_map(|(name, signature)| signature.captures(first_line).map(|c| (*name, c)))?;
+    let mut service = Service {
+        name: name.to_string(),
+        product: captures.name("product").map(|m| m.as_str().to_string()),
+        version: captures.name("version").map(|m| m.as_str().to_string()),
+    };
+
+    match name {
+        "http" => {
+            if let Some(captures) = HTTP_SERVER.captures(&text) {
+                service.product = captures.name("product").map(|m| m.as_str().to_string());
+                service.version = captures.name("version").map(|m| m.as_str().to_string());
+            }
+        }
+        "redis" => {
+            service.product = Some(String::from("Redis"));
+            service.version = REDIS_VERSION
+                .captures(&text)
+                .and_then(|captures| captures.name("version"))
+                .map(|m| m.as_str().to_string());
+        }
+        _ => {}
+    }
+
+    Some(service)
+}
+
+/// The MySQL protocol starts with a binary greeting packet, or an error packet when the client isn't allowed to connect
+fn identify_mysql(response: &[u8]) -> Option<Service> {
+    if response.len() < 6 || response[3] != 0 {
+        return None;
+    }
+    let length = u32::from_le_bytes([response[0], response[1], response[2], 0]) as usize;
+    if length != response.len() - 4 {
+        return None;
+    }
+
+    match response[4] {
+        // Protocol version 10, followed by the null-terminated server version
+        0x0a => {
+            let version = response[5..].split(|b| *b == 0).next()?;
+            let version = String::from_utf8_lossy(version).to_string();
+            Some(Service {
+                name: String::from("mysql"),
+                product: Some(String::from(if version.contains("MariaDB") {
+                    "MariaDB"
+                } else {
+                    "MySQL"
+                })),
+                version: version.split('-').next().map(|v| v.to_string()),
+            })
+        }
+        0xff if String::from_utf8_lossy(response).contains("MySQL")
+            || String::from_utf8_lossy(response).contains("MariaDB") =>
+        {
+            Some(Service {
+                name: String::from("mysql"),
+                ..Default::default()
+            })
+        }
+        _ => None,
+    }
+}
+
+/// The service commonly running on a port, used when it couldn't be identified from its responses
+pub fn guess_from_port(port: usize) -> Option<&'static str> {
+    match port {
+        21 => Some("ftp"),
+        22 => Some("ssh"),
+        23 => Some("telnet"),
+        25 | 465 | 587 => Some("smtp"),
+        53 => Some("dns"),
+        80 | 8000 | 8008 | 8080 => Some("http"),
+        110 => Some("pop3"),
+        143 => Some("imap"),
+        443 | 8443 => Some("https"),
+        445 => Some("smb"),
+        993 => Some("imaps"),
+        995 => Some("pop3s"),
+        1433 => Some("mssql"),
+        3306 => Some("mysql"),
+        3389 => Some("rdp"),
+        5432 => Some("postgresql"),
+        5900 => Some("vnc"),
+        6379 => Some("redis"),
+        27017 => Some("mongodb"),
+        _ => None,
+    }
+}
@@ -0,0 +1,76 @@
+use crate::modules::service_detection::signatures::{self, Service};
+
+fn service(name: &str, product: Option<&str>, version: Option<&str>) -> Option<Service> {
+    Some(Service {
+        name: name.to_string(),
+        product: product.map(|p| p.to_string()),
+        version: version.map(|v| v.to_string()),
+    })
+}
+
+#[test]
+fn identify_ssh() {
+    assert_eq!(
+        signatures::identify(b"SSH-2.0-OpenSSH_8.9p1 Ubuntu-3ubuntu0.1\r\n"),
+        service("ssh", Some("OpenSSH"), Some("8.9p1"))
+    );
+    assert_eq!(
+        signatures::identify(b"SSH-2.0-dropbear_2020.81\r\n"),
+        service("ssh", Some("dropbear"), Some("2020.81"))
+    );
+}
+
+#[test]
+fn identify_ftp_and_smtp() {
+    assert_eq!(
+        signatures::identify(b"220 (vsFTPd 3.0.3)\r\n"),
+        service("ftp", Some("vsFTPd"), Some("3.0.3"))
+    );
+    assert_eq!(
+        signatures::identify(b"220 ProFTPD 1.3.5 Server (Debian)\r\n"),
+        service("ftp", Some("ProFTPD"), Some("1.3.5"))
+    );
+    assert_eq!(
+        signatures::identify(b"220 mail.example.com ESMTP Postfix (Ubuntu)\r\n"),
+        service("smtp", Some("Postfix"), None)
+    );
+    assert_eq!(
+        signatures::identify(b"220 mx.example.com ESMTP Exim 4.94.2 Mon, 01 Jan 2024\r\n"),
+        service("smtp", Some("Exim"), Some("4.94.2"))
+    );
+}
+
+#[test]
+fn identify_mysql() {
+    let mut greeting = vec![0x0a];
+    greeting.extend_from_slice(b"8.0.32-0ubuntu0.22.04.2\0");
+    greeting.extend_from_slice(&[0x01, 0x02, 0x03, 0x04]);
+    let mut packet = vec![greeting.len() as u8, 0, 0, 0];
+    packet.extend(greeting);
+    assert_eq!(
+        signatures::identify(&packet),
+        service("mysql", Some("MySQL"), Some("8.0.32"))
+    );
+}
+
+#[test]
+fn identify_probed_services() {
+    assert_eq!(
+        signatures::identify(b"HTTP/1.1 200 OK\r\nServer: nginx/1.18.0 (Ubuntu)\r\n\r\n"),
+        service("http", Some("nginx"), Some("1.18.0"))
+    );
+    assert_eq!(
+        signatures::identify(b"+PONG\r\n$100\r\n# Server\r\nredis_version:7.0.5\r\n"),
+        service("redis", Some("Redis"), Some("7.0.5"))
+    );
+    assert_eq!(
+        signatures::identify(b"-NOAUTH Authentication required.\r\n"),
+        service("redis", Some("Redis"), None)
+    );
+}
+
+#[test]
+fn identify_unknown() {
+    assert_eq!(signatures::identify(b"\x00\x01\x02"), None);
+    assert_eq!(signatures::identify(b"hello"), None);
+}
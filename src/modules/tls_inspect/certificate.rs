@@ -0,0 +1,100 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use x509_parser::objects::{oid_registry, oid2sn};
+use x509_parser::prelude::{FromDer, GeneralName, X509Certificate};
+use x509_parser::public_key::PublicKey;
+
+#[derive(Debug, Serialize)]
+pub struct Certificate {
+    pub subject: String,
+    pub issuer: String,
+    pub serial: String,
+    pub not_before: String,
+    pub not_after: String,
+    pub expired: bool,
+    pub key_type: String,
+    pub key_size: usize,
+    pub signature_algorithm: String,
+    pub sans: Vec<String>,
+    /// The subjects of the presented chain, starting with the leaf certificate
+    pub chain: Vec<String>,
+    pub trusted: bool,
+    pub hostname_match: bool,
+    pub error: Option<String>,
+}
+
+fn format_timestamp(timestamp: i64) -> String {
+    DateTime::<Utc>::from_timestamp(timestamp, 0)
+        .map(|date| date.to_rfc3339())
+        .unwrap_or_default()
+}
+
+/// Parses the leaf certificate of a presented chain, the trust related fields are left to be set by the caller
+pub fn parse(chain: &[Vec<u8>]) -> Result<Certificate, String> {
+    let leaf = chain.first().ok_or("No certificate has been presented")?;
+    let (_, certificate) =
+        X509Certificate::from_der(leaf).map_err(|e| format!("Invalid certificate: {}", e))?;
+
+    let public_key = certificate.public_key();
+    let (key_type, key_size) = match public_key.parsed() {
+        Ok(PublicKey::RSA(key)) => (String::from("RSA"), key.key_size()),
+        Ok(PublicKey::EC(key)) => (String::from("EC"), key.key_size()),
+        Ok(PublicKey::DSA(_)) => (String::from("DSA"), 0),
+        _ => (
+            oid2sn(&public_key.algorithm.algorithm, oid_registry())
+                .unwrap_or("unknown")
+                .to_string(),
+            0,
+        ),
+    };
+
+    let sans = match certificate.subject_alternative_name() {
+        Ok(Some(extension)) => extension
+            .value
+            .general_names
+            .iter()
+            .filter_map(|name| match name {
+                GeneralName::DNSName(name) => Some(name.to_lowercase()),
+                _ => None,
+            })
+            .collect(),
+        _ => Vec::new(),
+    };
+
+    let chain = chain
+        .iter()
+        .filter_map(|der| X509Certificate::from_der(der).ok())
+        .map(|(_, certificate)| certificate.subject().to_string())
+        .collect();
+
+    let not_after = certificate.validity().not_after.timestamp();
+    Ok(Certificate {
+        subject: certificate.subject().to_string(),
+        issuer: certificate.issuer().to_string(),
+        serial: certificate.raw_serial_as_string(),
+        not_before: format_timestamp(certificate.validity().not_before.timestamp()),
+        not_after: format_timestamp(not_after),
+        expired: not_after < Utc::now().timestamp(),
+        key_type,
+        key_size,
+        signature_algorithm: oid2sn(&certificate.signature_algorithm.algorithm, oid_registry())
+            .unwrap_or("unknown")
+            .to_string(),
+        sans,
+        chain,
+        trusted: false,
+        hostname_match: false,
+        error: None,
+    })
+}
+
+/// Whether the hostname is covered by one of the names, which may contain a wildcard as their left-most label
+pub fn matches_hostname(names: &[String], hostname: &str) -> bool {
+    let hostname = hostname.to_lowercase();
+    names.iter().any(|name| match name.strip_prefix("*.") {
+        Some(parent) => hostname
+            .split_once('.')
+            .is_some_and(|(label, rest)| !label.is_empty() && rest == parent),
+        None => *name == hostname,
+    })
+}
@@ -0,0 +1,260 @@
+use std::net::{IpAddr, SocketAddr, TcpStream};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use rustls::client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier};
+use rustls::client::{ClientConnection, WebPkiServerVerifier};
+use rustls::crypto::{CryptoProvider, ring};
+use rustls::pki_types::{CertificateDer, ServerName, UnixTime};
+use rustls::{
+    CertificateError, ClientConfig, DigitallySignedStruct, RootCertStore, SignatureScheme,
+};
+
+use crate::database::node::Type;
+use crate::modules::{Context, Module};
+use crate::session::Session;
+use crate::{config, events, flags, logger};
+
+mod certificate;
+#[cfg(test)]
+mod tests;
+
+/// Accepts any certificate so that invalid ones can be inspected as well, they are verified separately
+#[derive(Debug)]
+struct AcceptAnyCertificate(Arc<CryptoProvider>);
+
+impl ServerCertVerifier for AcceptAnyCertificate {
+    fn verify_server_cert(
+        &self,
+        _: &CertificateDer<'_>,
+        _: &[CertificateDer<'_>],
+        _: &ServerName<'_>,
+        _: &[u8],
+        _: UnixTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        Ok(ServerCertVerified::assertion())
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        rustls::crypto::verify_tls12_signature(
+            message,
+            cert,
+            dss,
+            &self.0.signature_verification_algorithms,
+        )
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        rustls::crypto::verify_tls13_signature(
+            message,
+            cert,
+            dss,
+            &self.0.signature_verification_algorithms,
+        )
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.0.signature_verification_algorithms.supported_schemes()
+    }
+}
+
+pub struct ModuleTlsInspect {
+    config: config::TlsInspectConfig,
+    client_config: Arc<ClientConfig>,
+    verifier: Arc<WebPkiServerVerifier>,
+    processed_targets: Mutex<Vec<String>>,
+}
+
+impl ModuleTlsInspect {
+    pub fn new(config: config::TlsInspectConfig) -> Result<Self, String> {
+        let provider = Arc::new(ring::default_provider());
+        let client_config = ClientConfig::builder_with_provider(Arc::clone(&provider))
+            .with_safe_default_protocol_versions()
+            .map_err(|e| e.to_string())?
+            .dangerous()
+            .with_custom_certificate_verifier(Arc::new(AcceptAnyCertificate(Arc::clone(&provider))))
+            .with_no_client_auth();
+        let roots = RootCertStore {
+            roots: webpki_roots::TLS_SERVER_ROOTS.to_vec(),
+        };
+        let verifier = WebPkiServerVerifier::builder_with_provider(Arc::new(roots), provider)
+            .build()
+            .map_err(|e| e.to_string())?;
+        Ok(ModuleTlsInspect {
+            config,
+            client_config: Arc::new(client_config),
+            verifier,
+            processed_targets: Mutex::new(Vec::new()),
+        })
+    }
+
+    pub fn process(&self, target: String) {
+        self.processed_targets.lock().unwrap().push(target)
+    }
+
+    pub fn has_processed(&self, target: String) -> bool {
+        self.processed_targets.lock().unwrap().contains(&target)
+    }
+
+    /// Performs a handshake and returns the certificate chain presented by the server, or `None` if it can't be reached
+    fn get_chain(
+        &self,
+        domain: &str,
+        ip_addr: IpAddr,
+        port: u16,
+    ) -> Result<Option<Vec<CertificateDer<'static>>>, String> {
+        let timeout = Duration::from_millis(self.config.timeout.unwrap_or(5000));
+        let Ok(mut socket) = TcpStream::connect_timeout(&SocketAddr::new(ip_addr, port), timeout)
+        else {
+            return Ok(None);
+        };
+        let _ = socket.set_read_timeout(Some(timeout));
+        let _ = socket.set_write_timeout(Some(timeout));
+
+        let server_name = ServerName::try_from(domain.to_string()).map_err(|e| e.to_string())?;
+        let mut connection = ClientConnection::new(Arc::clone(&self.client_config), server_name)
+            .map_err(|e| e.to_string())?;
+        while connection.is_handshaking() {
+            connection
+                .complete_io(&mut socket)
+                .map_err(|e| e.to_string())?;
+        }
+        Ok(Some(
+            connection
+                .peer_certificates()
+                .map(|chain| chain.iter().map(|der| der.clone().into_owned()).collect())
+                .unwrap_or_default(),
+        ))
+    }
+
+    fn inspect(
+        &self,
+        domain: &str,
+        ip_addr: IpAddr,
+        port: u16,
+    ) -> Result<Option<certificate::Certificate>, String> {
+        let Some(chain) = self.get_chain(domain, ip_addr, port)? else {
+            return Ok(None);
+        };
+        let mut certificate = certificate::parse(
+            &chain
+                .iter()
+                .map(|der| der.as_ref().to_vec())
+                .collect::<Vec<Vec<u8>>>(),
+        )?;
+        certificate.hostname_match = certificate::matches_hostname(&certificate.sans, domain);
+
+        let server_name = ServerName::try_from(domain.to_string()).map_err(|e| e.to_string())?;
+        match self.verifier.verify_server_cert(
+            &chain[0],
+            &chain[1..],
+            &server_name,
+            &[],
+            UnixTime::now(),
+        ) {
+            Ok(_) => certificate.trusted = true,
+            Err(rustls::Error::InvalidCertificate(
+                CertificateError::NotValidForName | CertificateError::NotValidForNameContext { .. },
+            )) => certificate.trusted = true,
+            Err(e) => certificate.error = Some(e.to_string()),
+        }
+        Ok(Some(certificate))
+    }
+}
+
+impl Module for ModuleTlsInspect {
+    fn name(&self) -> String {
+        String::from("tls:inspect")
+    }
+
+    fn description(&self) -> String {
+        String::from(
+            "This module inspects the TLS certificate of a domain and discovers new domains from its alternative names",
+        )
+    }
+
+    fn subscribers(&self) -> Vec<events::Type> {
+        vec![
+            events::Type::DiscoveredDomain(String::new()),
+            events::Type::OpenPort(String::new(), 0),
+        ]
+    }
+
+    fn execute(&self, session: &Session, context: Context) -> Result<(), String> {
+        let (domain, port) = match context {
+            Context::Domain(domain) => (domain, 443),
+            Context::Port(domain, port) => (domain, port as u16),
+            _ => {
+                return Err("Received wrong context, exiting module".to_string());
+            }
+        };
+        let is_default_port = port == 443;
+
+        let target = format!("{}:{}", domain, port);
+        if self.has_processed(target.clone()) {
+            return Ok(());
+        }
+        self.process(target.clone());
+
+        let Some(ip_addr) = session.get_resolver().lookup_ip(&domain).first().copied() else {
+            return Ok(());
+        };
+        let certificate = match self.inspect(&domain, ip_addr, port) {
+            Ok(Some(certificate)) => certificate,
+            Ok(None) => return Ok(()),
+            // Other open ports aren't necessarily using TLS
+            Err(_) if !is_default_port => return Ok(()),
+            Err(e) => return Err(format!("Failed inspecting '{}': {}", target, e)),
+        };
+        logger::println(
+            self.name(),
+            format!(
+                "Inspected the certificate of '{}' issued by '{}'{}{}",
+                target,
+                certificate.issuer,
+                if certificate.expired {
+                    " $[fg:red]$[effect:bold](Expired)"
+                } else {
+                    ""
+                },
+                if !certificate.trusted || !certificate.hostname_match {
+                    " $[fg:red]$[effect:bold](Invalid)"
+                } else {
+                    ""
+                }
+            ),
+        );
+
+        let sans = certificate.sans.clone();
+        if let Some(node) = session.get_database().search(Type::Domain, domain.clone()) {
+            if certificate.expired {
+                node.add_flag(flags::domain::HAS_EXPIRED);
+            }
+            let mut tls = node.get_or_init_map("tls");
+            tls.insert(
+                port.to_string(),
+                serde_json::to_value(&certificate).map_err(|e| e.to_string())?,
+            );
+            node.add_data(String::from("tls"), tls.into());
+        }
+
+        for san in sans {
+            let san = san.strip_prefix("*.").unwrap_or(&san).to_string();
+            if san != domain && session.is_in_scope(&san) {
+                session.discover_domain(&self.name(), san, Some(&domain));
+            }
+        }
+
+        Ok(())
+    }
+}
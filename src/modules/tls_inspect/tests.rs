@@ -0,0 +1,22 @@
+use crate::modules::tls_inspect::certificate::matches_hostname;
+
+fn names(names: &[&str]) -> Vec<String> {
+    names.iter().map(|name| name.to_string()).collect()
+}
+
+#[test]
+fn match_exact_hostname() {
+    let sans = names(&["krypton.ninja", "go.krypton.ninja"]);
+    assert!(matches_hostname(&sans, "krypton.ninja"));
+    assert!(matches_hostname(&sans, "Go.Krypton.Ninja"));
+    assert!(!matches_hostname(&sans, "status.krypton.ninja"));
+}
+
+#[test]
+fn match_wildcard_hostname() {
+    let sans = names(&["*.krypton.ninja"]);
+    assert!(matches_hostname(&sans, "go.krypton.ninja"));
+    assert!(!matches_hostname(&sans, "krypton.ninja"));
+    assert!(!matches_hostname(&sans, "a.go.krypton.ninja"));
+    assert!(!matches_hostname(&sans, "krypton.ninja.evil.com"));
+}
@@ -0,0 +1,256 @@
+use std::io::{Read, Write};
+
+use rand::RngCore;
+
+pub const SSL3: u16 = 0x0300;
+pub const TLS10: u16 = 0x0301;
+pub const TLS11: u16 = 0x0302;
+pub const TLS12: u16 = 0x0303;
+pub const TLS13: u16 = 0x0304;
+
+const CONTENT_TYPE_ALERT: u8 = 21;
+const CONTENT_TYPE_HANDSHAKE: u8 = 22;
+const CONTENT_TYPE_HEARTBEAT: u8 = 24;
+
+const HANDSHAKE_CLIENT_HELLO: u8 = 1;
+const HANDSHAKE_SERVER_HELLO: u8 = 2;
+const HANDSHAKE_SERVER_HELLO_DONE: u8 = 14;
+
+const EXTENSION_SERVER_NAME: u16 = 0x0000;
+const EXTENSION_SUPPORTED_GROUPS: u16 = 0x000a;
+const EXTENSION_EC_POINT_FORMATS: u16 = 0x000b;
+const EXTENSION_SIGNATURE_ALGORITHMS: u16 = 0x000d;
+const EXTENSION_HEARTBEAT: u16 = 0x000f;
+const EXTENSION_SUPPORTED_VERSIONS: u16 = 0x002b;
+const EXTENSION_KEY_SHARE: u16 = 0x0033;
+
+/// x25519, secp256r1, secp384r1, secp521r1 and ffdhe2048
+const SUPPORTED_GROUPS: [u16; 5] = [0x001d, 0x0017, 0x0018, 0x0019, 0x0100];
+
+/// The ECDSA, RSA-PSS and RSA PKCS#1 schemes, including the SHA-1 ones still used by older servers
+const SIGNATURE_ALGORITHMS: [u16; 11] = [
+    0x0403, 0x0503, 0x0603, 0x0804, 0x0805, 0x0806, 0x0401, 0x0501, 0x0601, 0x0201, 0x0203,
+];
+
+/// The maximum amount of records read before giving up on a handshake
+const MAX_RECORDS: usize = 64;
+
+#[derive(Debug, PartialEq)]
+pub struct ServerHello {
+    /// The negotiated version, taken from the `supported_versions` extension for TLS 1.3
+    pub version: u16,
+    pub cipher_suite: u16,
+    pub heartbeat: bool,
+}
+
+pub fn get_protocol_name(version: u16) -> &'static str {
+    match version {
+        SSL3 => "SSLv3",
+        TLS10 => "TLSv1.0",
+        TLS11 => "TLSv1.1",
+        TLS12 => "TLSv1.2",
+        TLS13 => "TLSv1.3",
+        _ => "unknown",
+    }
+}
+
+fn push_u16(buffer: &mut Vec<u8>, value: u16) {
+    buffer.extend_from_slice(&value.to_be_bytes());
+}
+
+fn push_u24(buffer: &mut Vec<u8>, value: usize) {
+    buffer.extend_from_slice(&(value as u32).to_be_bytes()[1..]);
+}
+
+fn push_extension(buffer: &mut Vec<u8>, extension: u16, data: &[u8]) {
+    push_u16(buffer, extension);
+    push_u16(buffer, data.len() as u16);
+    buffer.extend_from_slice(data);
+}
+
+/// Builds a ClientHello record offering the given version and cipher suites
+pub fn client_hello(
+    hostname: &str,
+    version: u16,
+    cipher_suites: &[u16],
+    heartbeat: bool,
+) -> Vec<u8> {
+    let mut random = [0u8; 32];
+    rand::rng().fill_bytes(&mut random);
+
+    let mut body = Vec::new();
+    push_u16(&mut body, version.min(TLS12));
+    body.extend_from_slice(&random);
+    if version == TLS13 {
+        // A legacy session identifier is expected by middleboxes
+        body.push(32);
+        body.extend_from_slice(&random);
+    } else {
+        body.push(0);
+    }
+    push_u16(&mut body, (cipher_suites.len() * 2) as u16);
+    for cipher_suite in cipher_suites {
+        push_u16(&mut body, *cipher_suite);
+    }
+    // Only the null compression method
+    body.extend_from_slice(&[1, 0]);
+
+    if version >= TLS10 {
+        let mut extensions = Vec::new();
+        if hostname.parse::<std::net::IpAddr>().is_err() {
+            let mut server_name = Vec::new();
+            push_u16(&mut server_name, (hostname.len() + 3) as u16);
+            server_name.push(0);
+            push_u16(&mut server_name, hostname.len() as u16);
+            server_name.extend_from_slice(hostname.as_bytes());
+            push_extension(&mut extensions, EXTENSION_SERVER_NAME, &server_name);
+        }
+
+        let mut groups = Vec::new();
+        push_u16(&mut groups, (SUPPORTED_GROUPS.len() * 2) as u16);
+        for group in SUPPORTED_GROUPS {
+            push_u16(&mut groups, group);
+        }
+        push_extension(&mut extensions, EXTENSION_SUPPORTED_GROUPS, &groups);
+        push_extension(&mut extensions, EXTENSION_EC_POINT_FORMATS, &[1, 0]);
+
+        let mut algorithms = Vec::new();
+        push_u16(&mut algorithms, (SIGNATURE_ALGORITHMS.len() * 2) as u16);
+        for algorithm in SIGNATURE_ALGORITHMS {
+            push_u16(&mut algorithms, algorithm);
+        }
+        push_extension(&mut extensions, EXTENSION_SIGNATURE_ALGORITHMS, &algorithms);
+
+        if heartbeat {
+            // The peer is allowed to send heartbeat requests
+            push_extension(&mut extensions, EXTENSION_HEARTBEAT, &[1]);
+        }
+        if version == TLS13 {
+            push_extension(
+                &mut extensions,
+                EXTENSION_SUPPORTED_VERSIONS,
+                &[2, 0x03, 0x04],
+            );
+            // No key share is sent, the server will answer with a HelloRetryRequest which is enough to know what it supports
+            push_extension(&mut extensions, EXTENSION_KEY_SHARE, &[0, 0]);
+        }
+
+        push_u16(&mut body, extensions.len() as u16);
+        body.extend(extensions);
+    }
+
+    let mut handshake = vec![HANDSHAKE_CLIENT_HELLO];
+    push_u24(&mut handshake, body.len());
+    handshake.extend(body);
+
+    let mut record = vec![CONTENT_TYPE_HANDSHAKE];
+    push_u16(&mut record, version.clamp(SSL3, TLS10));
+    push_u16(&mut record, handshake.len() as u16);
+    record.extend(handshake);
+    record
+}
+
+/// Parses the body of a ServerHello handshake message
+pub fn parse_server_hello(body: &[u8]) -> Option<ServerHello> {
+    let mut version = u16::from_be_bytes([*body.first()?, *body.get(1)?]);
+    let session_id_length = *body.get(34)? as usize;
+    let mut offset = 35 + session_id_length;
+    let cipher_suite = u16::from_be_bytes([*body.get(offset)?, *body.get(offset + 1)?]);
+    // Skip the cipher suite and the compression method
+    offset += 3;
+
+    let mut heartbeat = false;
+    if let (Some(high), Some(low)) = (body.get(offset), body.get(offset + 1)) {
+        let end = (offset + 2 + u16::from_be_bytes([*high, *low]) as usize).min(body.len());
+        offset += 2;
+        while offset + 4 <= end {
+            let extension = u16::from_be_bytes([body[offset], body[offset + 1]]);
+            let length = u16::from_be_bytes([body[offset + 2], body[offset + 3]]) as usize;
+            let data = body.get(offset + 4..offset + 4 + length)?;
+            match extension {
+                EXTENSION_SUPPORTED_VERSIONS if length == 2 => {
+                    version = u16::from_be_bytes([data[0], data[1]]);
+                }
+                EXTENSION_HEARTBEAT => heartbeat = true,
+                _ => {}
+            }
+            offset += 4 + length;
+        }
+    }
+
+    Some(ServerHello {
+        version,
+        cipher_suite,
+        heartbeat,
+    })
+}
+
+fn read_record(stream: &mut impl Read) -> Option<(u8, Vec<u8>)> {
+    let mut header = [0u8; 5];
+    stream.read_exact(&mut header).ok()?;
+    let length = u16::from_be_bytes([header[3], header[4]]) as usize;
+    let mut body = vec![0u8; length];
+    stream.read_exact(&mut body).ok()?;
+    Some((header[0], body))
+}
+
+/// Whether the header of the first record sent by the server is the one of a TLS handshake or alert, which even a server refusing the ClientHello sends
+pub fn is_tls_record(header: &[u8]) -> bool {
+    header.len() == 5
+        && [CONTENT_TYPE_HANDSHAKE, CONTENT_TYPE_ALERT].contains(&header[0])
+        && header[1] == 3
+}
+
+/// Reads the handshake messages of the server until its ServerHello, or until its ServerHelloDone if asked to
+pub fn read_server_hello(stream: &mut impl Read, until_done: bool) -> Option<ServerHello> {
+    let mut handshake = Vec::new();
+    let mut server_hello = None;
+    for _ in 0..MAX_RECORDS {
+        let (content_type, body) = read_record(stream)?;
+        match content_type {
+            CONTENT_TYPE_HANDSHAKE => handshake.extend(body),
+            CONTENT_TYPE_ALERT => return None,
+            _ => return server_hello,
+        }
+
+        // Handshake messages may span multiple records, or a record may contain multiple messages
+        while handshake.len() >= 4 {
+            let length = u32::from_be_bytes([0, handshake[1], handshake[2], handshake[3]]) as usize;
+            if handshake.len() < 4 + length {
+                break;
+            }
+            let message = handshake.drain(..4 + length).collect::<Vec<u8>>();
+            match message[0] {
+                HANDSHAKE_SERVER_HELLO => {
+                    server_hello = parse_server_hello(&message[4..]);
+                    if !until_done || server_hello.is_none() {
+                        return server_hello;
+                    }
+                }
+                HANDSHAKE_SERVER_HELLO_DONE => return server_hello,
+                _ => {}
+            }
+        }
+    }
+    server_hello
+}
+
+/// Sends a heartbeat request claiming a larger payload than the one sent, only a vulnerable server will answer it
+pub fn probe_heartbleed(stream: &mut (impl Read + Write), version: u16) -> bool {
+    let mut record = vec![CONTENT_TYPE_HEARTBEAT];
+    push_u16(&mut record, version);
+    push_u16(&mut record, 3);
+    // Request type followed by a payload length of 16 bytes, without any payload
+    record.extend_from_slice(&[1, 0, 16]);
+    if stream.write_all(&record).is_err() {
+        return false;
+    }
+    for _ in 0..MAX_RECORDS {
+        match read_record(stream) {
+            Some((CONTENT_TYPE_HEARTBEAT, _)) => return true,
+            Some((CONTENT_TYPE_HANDSHAKE, _)) => continue,
+            _ => return false,
+        }
+    }
+    false
+}
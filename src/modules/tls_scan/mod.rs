@@ -0,0 +1,379 @@
+use std::collections::BTreeMap;
+use std::io::{Read, Write};
+use std::net::{IpAddr, SocketAddr, TcpStream};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use serde::Serialize;
+
+use crate::database::node::Type;
+use crate::findings::{Finding, Severity};
+use crate::modules::tls_scan::handshake::ServerHello;
+use crate::modules::{Context, Module};
+use crate::session::Session;
+use crate::{config, events, logger};
+
+mod ciphers;
+mod handshake;
+#[cfg(test)]
+mod tests;
+
+#[derive(Debug, Default, Serialize)]
+pub struct ScanResult {
+    /// The cipher suites accepted for each protocol, an empty list meaning the protocol isn't supported
+    pub protocols: BTreeMap<String, Vec<String>>,
+    pub heartbleed: bool,
+}
+
+impl ScanResult {
+    fn supports(&self, protocol: &str) -> bool {
+        self.protocols
+            .get(protocol)
+            .is_some_and(|cipher_suites| !cipher_suites.is_empty())
+    }
+
+    fn get_cipher_suites(&self, filter: fn(&str) -> bool) -> Vec<String> {
+        let mut cipher_suites = self
+            .protocols
+            .values()
+            .flatten()
+            .filter(|name| filter(name))
+            .cloned()
+            .collect::<Vec<String>>();
+        cipher_suites.sort();
+        cipher_suites.dedup();
+        cipher_suites
+    }
+}
+
+pub struct ModuleTlsScan {
+    config: config::TlsScanConfig,
+    processed_targets: Mutex<Vec<String>>,
+}
+
+impl ModuleTlsScan {
+    pub fn new(config: config::TlsScanConfig) -> Self {
+        ModuleTlsScan {
+            config,
+            processed_targets: Mutex::new(Vec::new()),
+        }
+    }
+
+    pub fn process(&self, target: String) {
+        self.processed_targets.lock().unwrap().push(target)
+    }
+
+    pub fn has_processed(&self, target: String) -> bool {
+        self.processed_targets.lock().unwrap().contains(&target)
+    }
+
+    fn connect(&self, address: SocketAddr) -> Option<TcpStream> {
+        let timeout = Duration::from_millis(self.config.timeout.unwrap_or(5000));
+        let stream = TcpStream::connect_timeout(&address, timeout).ok()?;
+        let _ = stream.set_read_timeout(Some(timeout));
+        let _ = stream.set_write_timeout(Some(timeout));
+        Some(stream)
+    }
+
+    /// Sends a ClientHello on a new connection and returns the ServerHello of the server, if any
+    fn handshake(
+        &self,
+        domain: &str,
+        address: SocketAddr,
+        version: u16,
+        cipher_suites: &[u16],
+    ) -> Option<ServerHello> {
+        let mut stream = self.connect(address)?;
+        stream
+            .write_all(&handshake::client_hello(
+                domain,
+                version,
+                cipher_suites,
+                false,
+            ))
+            .ok()?;
+        handshake::read_server_hello(&mut stream, false)
+    }
+
+    /// Whether the server answers a ClientHello with TLS, so that services not using it aren't scanned
+    fn probe(&self, domain: &str, address: SocketAddr) -> bool {
+        let Some(mut stream) = self.connect(address) else {
+            return false;
+        };
+        let cipher_suites = ciphers::LEGACY_CIPHER_SUITES.map(|(code, _)| code);
+        if stream
+            .write_all(&handshake::client_hello(
+                domain,
+                handshake::TLS12,
+                &cipher_suites,
+                false,
+            ))
+            .is_err()
+        {
+            return false;
+        }
+        let mut header = [0u8; 5];
+        stream.read_exact(&mut header).is_ok() && handshake::is_tls_record(&header)
+    }
+
+    /// Offers every known cipher suite and removes the one chosen by the server until it refuses the handshake
+    fn enumerate_cipher_suites(&self, domain: &str, address: SocketAddr, version: u16) -> Vec<u16> {
+        let mut remaining = if version == handshake::TLS13 {
+            ciphers::TLS13_CIPHER_SUITES.map(|(code, _)| code).to_vec()
+        } else {
+            ciphers::LEGACY_CIPHER_SUITES.map(|(code, _)| code).to_vec()
+        };
+        let mut accepted = Vec::new();
+        while !remaining.is_empty() {
+            let Some(server_hello) = self.handshake(domain, address, version, &remaining) else {
+                break;
+            };
+            if server_hello.version != version || !remaining.contains(&server_hello.cipher_suite) {
+                break;
+            }
+            remaining.retain(|code| *code != server_hello.cipher_suite);
+            accepted.push(server_hello.cipher_suite);
+        }
+        accepted
+    }
+
+    fn check_heartbleed(
+        &self,
+        domain: &str,
+        address: SocketAddr,
+        version: u16,
+        cipher_suites: &[u16],
+    ) -> bool {
+        let Some(mut stream) = self.connect(address) else {
+            return false;
+        };
+        if stream
+            .write_all(&handshake::client_hello(
+                domain,
+                version,
+                cipher_suites,
+                true,
+            ))
+            .is_err()
+        {
+            return false;
+        }
+        match handshake::read_server_hello(&mut stream, true) {
+            Some(server_hello) if server_hello.heartbeat => {
+                handshake::probe_heartbleed(&mut stream, server_hello.version)
+            }
+            _ => false,
+        }
+    }
+
+    fn scan(&self, domain: &str, ip_addr: IpAddr, port: u16) -> ScanResult {
+        let address = SocketAddr::new(ip_addr, port);
+        let mut result = ScanResult::default();
+        if !self.probe(domain, address) {
+            return result;
+        }
+        let mut best = None;
+        for version in [
+            handshake::SSL3,
+            handshake::TLS10,
+            handshake::TLS11,
+            handshake::TLS12,
+            handshake::TLS13,
+        ] {
+            let accepted = self.enumerate_cipher_suites(domain, address, version);
+            if !accepted.is_empty() && version != handshake::TLS13 {
+                best = Some((version, accepted.clone()));
+            }
+            result.protocols.insert(
+                handshake::get_protocol_name(version).to_string(),
+                accepted.into_iter().map(ciphers::get_name).collect(),
+            );
+        }
+        // Heartbeats are encrypted with TLS 1.3, only the older protocols can be probed
+        if let Some((version, cipher_suites)) = best {
+            result.heartbleed = self.check_heartbleed(domain, address, version, &cipher_suites);
+        }
+        result
+    }
+}
+
+/// Turns the results of a scan into findings about its weaknesses
+pub fn evaluate(module: &str, result: &ScanResult) -> Vec<Finding> {
+    let mut findings = Vec::new();
+    let mut add = |severity, title: &str, evidence: Vec<String>| {
+        findings.push(Finding::new(
+            module,
+            severity,
+            title,
+            (!evidence.is_empty()).then(|| evidence.join(", ")),
+        ))
+    };
+
+    if !result
+        .protocols
+        .values()
+        .any(|cipher_suites| !cipher_suites.is_empty())
+    {
+        return Vec::new();
+    }
+    if result.heartbleed {
+        add(
+            Severity::Critical,
+            "Vulnerable to Heartbleed (CVE-2014-0160)",
+            Vec::new(),
+        );
+    }
+    if result.supports("SSLv3") {
+        add(Severity::High, "SSLv3 is supported (POODLE)", Vec::new());
+    }
+    if result.supports("TLSv1.0") {
+        add(Severity::Medium, "TLS 1.0 is supported", Vec::new());
+    }
+    if result.supports("TLSv1.1") {
+        add(Severity::Low, "TLS 1.1 is supported", Vec::new());
+    }
+    if !result.supports("TLSv1.2") && !result.supports("TLSv1.3") {
+        add(
+            Severity::High,
+            "Neither TLS 1.2 nor TLS 1.3 is supported",
+            Vec::new(),
+        );
+    }
+
+    let rc4 = result.get_cipher_suites(ciphers::is_rc4);
+    if !rc4.is_empty() {
+        add(Severity::High, "RC4 cipher suites are accepted", rc4);
+    }
+    let export = result.get_cipher_suites(ciphers::is_export);
+    if !export.is_empty() {
+        add(
+            Severity::High,
+            "Export cipher suites are accepted (FREAK)",
+            export,
+        );
+    }
+    let null_or_anonymous = result.get_cipher_suites(ciphers::is_null_or_anonymous);
+    if !null_or_anonymous.is_empty() {
+        add(
+            Severity::High,
+            "NULL or anonymous cipher suites are accepted",
+            null_or_anonymous,
+        );
+    }
+    let weak_block_cipher = result.get_cipher_suites(ciphers::is_weak_block_cipher);
+    if !weak_block_cipher.is_empty() {
+        add(
+            Severity::Medium,
+            "DES or 3DES cipher suites are accepted (SWEET32)",
+            weak_block_cipher,
+        );
+    }
+    if let Some(cipher_suites) = result.protocols.get("TLSv1.0")
+        && !cipher_suites.is_empty()
+        && cipher_suites.iter().all(|name| ciphers::is_cbc(name))
+    {
+        add(
+            Severity::Low,
+            "Only CBC cipher suites are accepted with TLS 1.0 (BEAST)",
+            Vec::new(),
+        );
+    }
+    if result
+        .get_cipher_suites(ciphers::has_forward_secrecy)
+        .is_empty()
+    {
+        add(
+            Severity::Medium,
+            "No cipher suite with forward secrecy is accepted",
+            Vec::new(),
+        );
+    }
+    findings
+}
+
+impl Module for ModuleTlsScan {
+    fn name(&self) -> String {
+        String::from("tls:scan")
+    }
+
+    fn description(&self) -> String {
+        String::from(
+            "This module scans the supported TLS protocols and cipher suites of a domain and reports weak configurations",
+        )
+    }
+
+    fn subscribers(&self) -> Vec<events::Type> {
+        vec![
+            events::Type::DiscoveredDomain(String::new()),
+            events::Type::OpenPort(String::new(), 0),
+        ]
+    }
+
+    fn execute(&self, session: &Session, context: Context) -> Result<(), String> {
+        let (domain, port) = match context {
+            Context::Domain(domain) => (domain, 443),
+            Context::Port(domain, port) => (domain, port as u16),
+            _ => {
+                return Err("Received wrong context, exiting module".to_string());
+            }
+        };
+
+        let target = format!("{}:{}", domain, port);
+        if self.has_processed(target.clone()) {
+            return Ok(());
+        }
+        self.process(target.clone());
+
+        let Some(ip_addr) = session.get_resolver().lookup_ip(&domain).first().copied() else {
+            return Ok(());
+        };
+        let result = self.scan(&domain, ip_addr, port);
+        // Either closed or not using TLS
+        if !result
+            .protocols
+            .values()
+            .any(|cipher_suites| !cipher_suites.is_empty())
+        {
+            return Ok(());
+        }
+
+        let findings = evaluate(&self.name(), &result);
+        logger::println(
+            self.name(),
+            format!(
+                "Scanned the TLS configuration of '{}' supporting {}{}",
+                target,
+                result
+                    .protocols
+                    .iter()
+                    .filter(|(_, cipher_suites)| !cipher_suites.is_empty())
+                    .map(|(protocol, _)| protocol.as_str())
+                    .collect::<Vec<&str>>()
+                    .join(", "),
+                if findings.is_empty() {
+                    String::new()
+                } else {
+                    format!(" $[fg:red]$[effect:bold]({} weaknesses)", findings.len())
+                }
+            ),
+        );
+
+        if let Some(node) = session.get_database().search(Type::Domain, domain.clone()) {
+            let mut tls_scan = node.get_or_init_map("tls_scan");
+            tls_scan.insert(
+                port.to_string(),
+                serde_json::to_value(&result).map_err(|e| e.to_string())?,
+            );
+            node.add_data(String::from("tls_scan"), tls_scan.into());
+            for mut finding in findings {
+                finding.evidence = Some(match finding.evidence {
+                    Some(evidence) => format!("{} ({})", target, evidence),
+                    None => target.clone(),
+                });
+                node.add_finding(finding);
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,91 @@
+use crate::findings::Severity;
+use crate::modules::tls_scan::handshake::{self, ServerHello};
+use crate::modules::tls_scan::{ScanResult, evaluate};
+
+fn result(protocols: &[(&str, &[&str])]) -> ScanResult {
+    ScanResult {
+        protocols: protocols
+            .iter()
+            .map(|(protocol, cipher_suites)| {
+                (
+                    protocol.to_string(),
+                    cipher_suites.iter().map(|name| name.to_string()).collect(),
+                )
+            })
+            .collect(),
+        heartbleed: false,
+    }
+}
+
+#[test]
+fn parse_tls13_server_hello() {
+    let mut body = vec![0x03, 0x03];
+    body.extend_from_slice(&[0; 32]);
+    body.push(0);
+    body.extend_from_slice(&[0x13, 0x01, 0x00]);
+    // supported_versions selecting TLS 1.3
+    body.extend_from_slice(&[0x00, 0x06, 0x00, 0x2b, 0x00, 0x02, 0x03, 0x04]);
+    assert_eq!(
+        handshake::parse_server_hello(&body),
+        Some(ServerHello {
+            version: handshake::TLS13,
+            cipher_suite: 0x1301,
+            heartbeat: false,
+        })
+    );
+    assert_eq!(handshake::parse_server_hello(&body[..20]), None);
+}
+
+#[test]
+fn read_server_hello_from_alert() {
+    let alert = [21, 0x03, 0x03, 0x00, 0x02, 0x02, 0x28];
+    assert_eq!(handshake::read_server_hello(&mut &alert[..], false), None);
+}
+
+#[test]
+fn evaluate_modern_configuration() {
+    let result = result(&[
+        ("SSLv3", &[]),
+        ("TLSv1.0", &[]),
+        ("TLSv1.1", &[]),
+        ("TLSv1.2", &["TLS_ECDHE_RSA_WITH_AES_128_GCM_SHA256"]),
+        ("TLSv1.3", &["TLS_AES_128_GCM_SHA256"]),
+    ]);
+    assert!(evaluate("tls:scan", &result).is_empty());
+}
+
+#[test]
+fn evaluate_legacy_configuration() {
+    let result = result(&[
+        ("SSLv3", &["TLS_RSA_WITH_RC4_128_SHA"]),
+        (
+            "TLSv1.0",
+            &["TLS_RSA_WITH_RC4_128_SHA", "TLS_RSA_WITH_3DES_EDE_CBC_SHA"],
+        ),
+        ("TLSv1.2", &[]),
+    ]);
+    let findings = evaluate("tls:scan", &result);
+    let titles = findings
+        .iter()
+        .map(|finding| (finding.severity, finding.title.as_str()))
+        .collect::<Vec<(Severity, &str)>>();
+    assert!(titles.contains(&(Severity::High, "SSLv3 is supported (POODLE)")));
+    assert!(titles.contains(&(Severity::Medium, "TLS 1.0 is supported")));
+    assert!(titles.contains(&(Severity::High, "Neither TLS 1.2 nor TLS 1.3 is supported")));
+    assert!(titles.contains(&(Severity::High, "RC4 cipher suites are accepted")));
+    assert!(titles.contains(&(
+        Severity::Medium,
+        "No cipher suite with forward secrecy is accepted"
+    )));
+    assert!(!titles.iter().any(|(_, title)| title.contains("BEAST")));
+}
+
+#[test]
+fn detect_tls_records() {
+    // A handshake, and an alert refusing the ClientHello
+    assert!(handshake::is_tls_record(&[0x16, 0x03, 0x03, 0x00, 0x5a]));
+    assert!(handshake::is_tls_record(&[0x15, 0x03, 0x01, 0x00, 0x02]));
+    // The banners of SSH and SMTP
+    assert!(!handshake::is_tls_record(b"SSH-2"));
+    assert!(!handshake::is_tls_record(b"220 m"));
+}
@@ -1,5 +1,6 @@
 use std::fs::{File, create_dir_all};
 use std::io::{Error, Write};
+use std::net::IpAddr;
 use std::path::PathBuf;
 use std::sync::mpsc::{Receiver, SyncSender};
 use std::sync::{Arc, Mutex, MutexGuard};
@@ -10,8 +11,9 @@ use clipboard::{ClipboardContext, ClipboardProvider};
 
 use reqwest::blocking::Client;
 
+use crate::database::node::{Node, Type};
 use crate::modules::Module;
-use crate::{args, config, database, debug, events, logger, modules, state};
+use crate::{args, config, database, debug, events, flags, helpers, logger, modules, state};
 
 pub struct Session {
     args: args::Args,
@@ -19,6 +21,9 @@ pub struct Session {
     database: Arc<Mutex<database::Database>>,
     state: Arc<state::State>,
     http_client: Client,
+    wildcards: helpers::wildcard::Wildcards,
+    resolver: helpers::dns::Resolver,
+    geoip: helpers::geoip::GeoIp,
 
     sender: SyncSender<events::Type>,
     receiver: Arc<Mutex<Receiver<events::Type>>>,
@@ -36,6 +41,13 @@ impl Session {
         let domain_clone = args.clone().domain;
         let is_verbose = args.verbose;
         let is_debug = args.debug;
+        let wildcard_probes = config
+            .wildcard
+            .as_ref()
+            .and_then(|config| config.probes)
+            .unwrap_or(3);
+        let resolver = helpers::dns::Resolver::new(config.resolver.as_ref());
+        let geoip = helpers::geoip::GeoIp::new(config.geoip.as_ref());
         Arc::new(Session {
             args,
             config,
@@ -44,6 +56,9 @@ impl Session {
             ))),
             state: Arc::new(state::State::new(is_verbose, is_debug)),
             http_client: Client::new(),
+            wildcards: helpers::wildcard::Wildcards::new(wildcard_probes),
+            resolver,
+            geoip,
 
             sender,
             receiver: Arc::new(Mutex::new(receiver)),
@@ -56,7 +71,7 @@ impl Session {
         &self.args
     }
 
-    pub fn get_database(&self) -> MutexGuard<database::Database> {
+    pub fn get_database(&self) -> MutexGuard<'_, database::Database> {
         self.database.lock().unwrap()
     }
 
@@ -68,6 +83,168 @@ impl Session {
         &self.http_client
     }
 
+    pub fn get_resolver(&self) -> &helpers::dns::Resolver {
+        &self.resolver
+    }
+
+    /// Whether the domain is the target domain or one of its subdomains
+    pub fn is_in_scope(&self, domain: &str) -> bool {
+        let target = &self.get_args().domain;
+        domain == target || domain.ends_with(&format!(".{}", target))
+    }
+
+    /// Whether the addresses a domain resolves to are the ones of a wildcard record of its parent domain
+    pub fn matches_wildcard(&self, domain: &str, ip_addrs: &[IpAddr]) -> bool {
+        self.wildcards
+            .matches(domain, ip_addrs, |name| self.resolver.lookup_ip(name))
+    }
+
+    /// Creates the node of a newly discovered domain with its resolved data, or `None` if it matches a wildcard record and those are configured to be dropped
+    pub fn new_domain_node(&self, domain: &str) -> Option<Node> {
+        self.new_resolved_domain_node(domain, self.resolver.lookup_ip(domain))
+    }
+
+    /// Same as `new_domain_node`, for a domain that was already resolved to the given addresses
+    pub fn new_resolved_domain_node(&self, domain: &str, ip_addrs: Vec<IpAddr>) -> Option<Node> {
+        let mut node = Node::new(Type::Domain, domain.to_string());
+        if self.matches_wildcard(domain, &ip_addrs) {
+            let drop = self
+                .config
+                .wildcard
+                .as_ref()
+                .and_then(|config| config.drop)
+                .unwrap_or(false);
+            if drop {
+                if self.get_state().is_debug_or_verbose() {
+                    logger::info(
+                        "wildcard",
+                        format!("Dropped '{}' as it matches a wildcard record", domain),
+                    );
+                }
+                // Other modules finding it again shouldn't resolve it and probe its parent once more
+                self.get_state().discover_domain(domain.to_string());
+                return None;
+            }
+            node.add_flag(flags::domain::WILDCARD);
+        }
+        for ip_addr in ip_addrs {
+            node.connect(self.new_ip_node(ip_addr));
+        }
+        Some(node)
+    }
+
+    /// Connects the node of a domain that wasn't found from one of its parents to its closest known parent, or to the target domain
+    pub fn connect_to_closest_parent(&self, domain: &str, node: Node) {
+        let mut database = self.get_database();
+        let parent = std::iter::successors(Some(domain), |domain| {
+            domain.split_once('.').map(|(_, parent)| parent)
+        })
+        .skip(1)
+        .find(|parent| database.search(Type::Domain, parent.to_string()).is_some())
+        .unwrap_or(&self.get_args().domain)
+        .to_string();
+        if let Some(parent) = database.search(Type::Domain, parent) {
+            parent.connect(node);
+        }
+    }
+
+    /// Adds the node of a newly discovered domain under the domain it was found from, or under its closest known parent when it wasn't found from one
+    pub fn add_domain_node(&self, module: &str, domain: String, node: Node, parent: Option<&str>) {
+        logger::println(
+            module,
+            format!(
+                "Discovered '{}' as a new subdomain{}{}{}",
+                domain,
+                if node.has_flag(flags::domain::HAS_EXPIRED) {
+                    " $[fg:red]$[effect:bold](Certificate expired, likely inactive)"
+                } else {
+                    ""
+                },
+                if node.has_flag(flags::domain::IS_RECENT) {
+                    " $[fg:blue]$[effect:bold](Active since less than 24 hours)"
+                } else {
+                    ""
+                },
+                if node.has_flag(flags::domain::WILDCARD) {
+                    " $[fg:yellow]$[effect:bold](Matches a wildcard record)"
+                } else {
+                    ""
+                }
+            ),
+        );
+
+        match parent {
+            Some(parent) => {
+                if let Some(parent) = self.get_database().search(Type::Domain, parent.to_string()) {
+                    parent.connect(node);
+                }
+            }
+            None => self.connect_to_closest_parent(&domain, node),
+        }
+        self.get_state().discover_domain(domain.clone());
+        self.emit(events::Type::DiscoveredDomain(domain));
+    }
+
+    /// Resolves and adds a domain that wasn't discovered yet, returning whether it was added
+    pub fn discover_domain(&self, module: &str, domain: String, parent: Option<&str>) -> bool {
+        if self.get_state().has_discovered_domain(domain.clone()) {
+            return false;
+        }
+        let ip_addrs = self.resolver.lookup_ip(&domain);
+        self.discover_resolved_domain(module, domain, ip_addrs, parent)
+    }
+
+    /// Same as `discover_domain`, for a domain that was already resolved to the given addresses
+    pub fn discover_resolved_domain(
+        &self,
+        module: &str,
+        domain: String,
+        ip_addrs: Vec<IpAddr>,
+        parent: Option<&str>,
+    ) -> bool {
+        if self.get_state().has_discovered_domain(domain.clone()) {
+            return false;
+        }
+        let Some(node) = self.new_resolved_domain_node(&domain, ip_addrs) else {
+            return false;
+        };
+        self.add_domain_node(module, domain, node, parent);
+        true
+    }
+
+    /// Adds an email that wasn't discovered yet under the domain it was found from
+    pub fn discover_email(&self, module: &str, email: String, domain: &str) {
+        if self.get_state().has_discovered_email(email.clone()) {
+            return;
+        }
+        logger::println(module, format!("Discovered '{}' as a new email", email));
+
+        if let Some(node) = self.get_database().search(Type::Domain, domain.to_string()) {
+            node.connect(Node::new(Type::Email, email.clone()));
+        }
+        self.get_state().discover_email(email);
+    }
+
+    /// Creates the node of an address with its location, connected to its autonomous system and network
+    pub fn new_ip_node(&self, ip_addr: IpAddr) -> Node {
+        let mut node = Node::new(Type::Ip, ip_addr.to_string());
+        let Some(geoinfo) = self.geoip.lookup(ip_addr) else {
+            return node;
+        };
+        if let Some(network) = &geoinfo.network {
+            node.connect(Node::new(Type::Netblock, network.clone()));
+        }
+        if let Some(asn) = geoinfo.asn {
+            let mut asn_node = Node::new(Type::Asn, format!("AS{}", asn));
+            if let Some(organization) = &geoinfo.as_organization {
+                asn_node.add_data(String::from("organization"), organization.clone().into());
+            }
+            node.connect(asn_node);
+        }
+        node.add_data(String::from("geoinfo"), geoinfo.into());
+        node
+    }
+
     pub fn register_module<T: Module + Send + Sync + 'static>(&self, module: T) {
         if self.get_state().is_debug_or_verbose() {
             logger::info("", format!("Registered module {}", module.name()))
@@ -89,15 +266,155 @@ impl Session {
             self.register_module(lua_module);
         }
 
-        if self.config.domain_takeover.is_some() {
-            self.register_module(modules::domain_takeover::ModuleDomainTakeover::new());
+        if let Some(config) = &self.config.cloud_detect
+            && config.enabled
+        {
+            match modules::cloud_detect::ModuleCloudDetect::new(config.clone()) {
+                Ok(module) => self.register_module(module),
+                Err(e) => logger::error("cloud:detect", e),
+            }
+        }
+        if let Some(config) = &self.config.dns_axfr
+            && config.enabled
+        {
+            self.register_module(modules::dns_axfr::ModuleDNSAxfr::new(config.clone()));
+        }
+        if let Some(config) = &self.config.dns_bruteforce
+            && config.enabled
+        {
+            self.register_module(modules::dns_bruteforce::ModuleDNSBruteforce::new(
+                config.clone(),
+            ));
+        }
+        if let Some(config) = &self.config.dns_permutations
+            && config.enabled
+        {
+            self.register_module(modules::dns_permutations::ModuleDNSPermutations::new(
+                config.clone(),
+            ));
+        }
+        if let Some(config) = &self.config.dns_records
+            && config.enabled
+        {
+            self.register_module(modules::dns_records::ModuleDNSRecords::new(config.clone()));
+        }
+        if let Some(config) = &self.config.dns_reverse
+            && config.enabled
+        {
+            self.register_module(modules::dns_reverse::ModuleDNSReverse::new(config.clone()));
+        }
+        if let Some(config) = &self.config.domain_takeover
+            && config.enabled
+        {
+            match modules::domain_takeover::ModuleDomainTakeover::new(config.clone()) {
+                Ok(module) => self.register_module(module),
+                Err(e) => logger::error("domain:takeover", e),
+            }
+        }
+        if let Some(config) = &self.config.domain_whois
+            && config.enabled
+        {
+            self.register_module(modules::domain_whois::ModuleDomainWhois::new(
+                config.clone(),
+            ));
         }
         if let Some(config) = &self.config.dork {
             self.register_module(modules::dork::ModuleDork::new(config.clone()));
         }
+        if let Some(config) = &self.config.email_security
+            && config.enabled
+        {
+            self.register_module(modules::email_security::ModuleEmailSecurity::new(
+                config.clone(),
+            ));
+        }
+        if let Some(config) = &self.config.http_bruteforce
+            && config.enabled
+        {
+            match modules::http_bruteforce::ModuleHttpBruteforce::new(config.clone()) {
+                Ok(module) => self.register_module(module),
+                Err(e) => logger::error("http:bruteforce", e),
+            }
+        }
+        if let Some(config) = &self.config.http_crawler
+            && config.enabled
+        {
+            match modules::http_crawler::ModuleHttpCrawler::new(config.clone()) {
+                Ok(module) => self.register_module(module),
+                Err(e) => logger::error("http:crawler", e),
+            }
+        }
+        if let Some(config) = &self.config.http_files
+            && config.enabled
+        {
+            match modules::http_files::ModuleHttpFiles::new(config.clone()) {
+                Ok(module) => self.register_module(module),
+                Err(e) => logger::error("http:files", e),
+            }
+        }
+        if let Some(config) = &self.config.http_headers
+            && config.enabled
+        {
+            match modules::http_headers::ModuleHttpHeaders::new(config.clone()) {
+                Ok(module) => self.register_module(module),
+                Err(e) => logger::error("http:headers", e),
+            }
+        }
+        if let Some(config) = &self.config.http_javascript
+            && config.enabled
+        {
+            match modules::http_javascript::ModuleHttpJavascript::new(config.clone()) {
+                Ok(module) => self.register_module(module),
+                Err(e) => logger::error("http:javascript", e),
+            }
+        }
+        if let Some(config) = &self.config.http_probe
+            && config.enabled
+        {
+            match modules::http_probe::ModuleHttpProbe::new(config.clone()) {
+                Ok(module) => self.register_module(module),
+                Err(e) => logger::error("http:probe", e),
+            }
+        }
+        if let Some(config) = &self.config.http_robots
+            && config.enabled
+        {
+            match modules::http_robots::ModuleHttpRobots::new(config.clone()) {
+                Ok(module) => self.register_module(module),
+                Err(e) => logger::error("http:robots", e),
+            }
+        }
         if let Some(config) = &self.config.passive_dns {
             self.register_module(modules::passive_dns::ModulePassiveDNS::new(config.clone()));
         }
+        if let Some(config) = &self.config.port_scan
+            && config.enabled
+        {
+            match modules::port_scan::ModulePortScan::new(config.clone()) {
+                Ok(module) => self.register_module(module),
+                Err(e) => logger::error("port:scan", e),
+            }
+        }
+        if let Some(config) = &self.config.tls_inspect
+            && config.enabled
+        {
+            match modules::tls_inspect::ModuleTlsInspect::new(config.clone()) {
+                Ok(module) => self.register_module(module),
+                Err(e) => logger::error("tls:inspect", e),
+            }
+        }
+        if let Some(config) = &self.config.tls_scan
+            && config.enabled
+        {
+            self.register_module(modules::tls_scan::ModuleTlsScan::new(config.clone()));
+        }
+        if let Some(config) = &self.config.service_detection
+            && config.enabled
+        {
+            self.register_module(modules::service_detection::ModuleServiceDetection::new(
+                config.clone(),
+            ));
+        }
     }
 
     pub fn emit(&self, event: events::Type) {
@@ -120,6 +437,13 @@ impl Session {
             });
         }
         self.emit(events::Type::Ready);
+        // The target domain isn't discovered, so its addresses are connected here
+        for ip_addr in self.get_resolver().lookup_ip(&self.get_args().domain) {
+            let ip_node = self.new_ip_node(ip_addr);
+            self.get_database().get_root().connect(ip_node);
+        }
+        self.get_state()
+            .discover_domain(self.get_args().domain.clone());
         self.emit(events::Type::DiscoveredDomain(
             self.get_args().domain.clone(),
         ));
@@ -192,11 +516,27 @@ impl Session {
                     if create_dir_all(markdown_result_path.parent().unwrap()).is_ok() {
                         let mut file_result = File::create(markdown_result_path.clone())?;
                         let domains_data = self.get_database().get_root().to_markdown();
-                        let content = format!(
+                        let hosts = self.get_database().get_hosts();
+                        let headers_summaries = self.get_database().get_headers_summaries();
+                        let mut content = format!(
                             "# Analysis Report for '{}'\n\n## Domains\n\n{}",
                             &self.get_args().domain,
                             domains_data
                         );
+                        if !hosts.is_empty() {
+                            content += format!(
+                                "\n\n## Infrastructure\n\n{}",
+                                database::hosts_to_markdown(&hosts)
+                            )
+                            .as_str();
+                        }
+                        if !headers_summaries.is_empty() {
+                            content += format!(
+                                "\n\n## Security Headers\n\n{}",
+                                database::headers_summaries_to_markdown(&headers_summaries)
+                            )
+                            .as_str();
+                        }
                         if file_result.write_all(content.as_bytes()).is_ok() {
                             logger::info(
                                 "",
@@ -223,6 +563,10 @@ impl Session {
                                 events::Type::DiscoveredDomain(_)
                             )
                             | (events::Type::OpenPort(_, _), events::Type::OpenPort(_, _))
+                            | (
+                                events::Type::LiveHttpHost(_, _),
+                                events::Type::LiveHttpHost(_, _)
+                            )
                     )
                 }) {
                     thread::spawn({
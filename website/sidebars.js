@@ -23,9 +23,28 @@ const sidebars = {
       type: "category",
       label: "Modules",
       items: [
+        "modules/cloud_detect",
+        "modules/dns_axfr",
+        "modules/dns_bruteforce",
+        "modules/dns_permutations",
+        "modules/dns_records",
+        "modules/dns_reverse",
         "modules/domain_takeover",
+        "modules/domain_whois",
         "modules/dork",
+        "modules/email_security",
+        "modules/http_bruteforce",
+        "modules/http_crawler",
+        "modules/http_files",
+        "modules/http_headers",
+        "modules/http_javascript",
+        "modules/http_probe",
+        "modules/http_robots",
         "modules/passive_dns",
+        "modules/port_scan",
+        "modules/service_detection",
+        "modules/tls_inspect",
+        "modules/tls_scan",
       ],
       collapsed: true,
     },
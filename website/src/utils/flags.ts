@@ -4,6 +4,11 @@ export enum DomainFlags {
   IS_RECENT = 1 << 0,
   HAS_EXPIRED = 1 << 1,
   POSSIBLE_TAKEOVER = 1 << 2,
+  WILDCARD = 1 << 3,
+  ZONE_TRANSFER = 1 << 4,
+  SPOOFABLE = 1 << 5,
+  DELEGATION_TAKEOVER = 1 << 6,
+  EXPIRING = 1 << 7,
 }
 
 export function checkFlag(flag: number, against: number): boolean {